@@ -0,0 +1,170 @@
+use {
+    crate::{
+        error::{AttestorError, Result},
+        ledger::Ledger,
+        report::{DeviceSignature, UsageReport},
+    },
+    screenwars_pinocchio::{client, SyncAttestation, SIGNATURE_LEN},
+    serde_json::json,
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    std::{collections::HashMap, path::Path},
+};
+
+// dev : where the attestor learns each challenge's daily_timer (seconds of screen time allowed per day)
+pub trait ChallengeSource {
+    fn daily_timer(&self, challenge_id: u32) -> Result<i64>;
+
+    // dev : the participant's on-chain user_pda.next_sync_day, None when the source can't read it
+    // and the ledger's last attestation stands in
+    fn next_sync_day(&self, _challenge_id: u32, _user: &Pubkey) -> Result<Option<u32>> {
+        Ok(None)
+    }
+}
+
+impl ChallengeSource for HashMap<u32, i64> {
+    fn daily_timer(&self, challenge_id: u32) -> Result<i64> {
+        self.get(&challenge_id)
+            .copied()
+            .ok_or(AttestorError::UnknownChallenge(challenge_id))
+    }
+}
+
+// dev : {"<challenge_id>": <daily_timer>, ..}
+pub fn load_challenges(path: impl AsRef<Path>) -> Result<HashMap<u32, i64>> {
+    let timers: HashMap<String, i64> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+
+    timers
+        .into_iter()
+        .map(|(id, timer)| {
+            id.parse()
+                .map(|id| (id, timer))
+                .map_err(|_| AttestorError::Config(format!("challenge id `{id}` is not a u32")))
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignedAttestation {
+    pub challenge_id: u32,
+    pub user: Pubkey,
+    pub day: u32,
+    pub attestation: SyncAttestation,
+    pub oracle: Pubkey,
+    pub signature: [u8; SIGNATURE_LEN],
+    pub device: Option<DeviceSignature>, // dev : forwarded from the report, SyncLock checks it against user_pda.device
+}
+
+impl SignedAttestation {
+    // dev : the exact bytes the oracle signed and SyncLock parses
+    pub fn message(&self) -> [u8; SyncAttestation::LEN] {
+        self.attestation.to_bytes()
+    }
+
+    // dev : the ed25519 instruction that goes right before the participant's sync_lock
+    pub fn instruction(&self) -> Instruction {
+        client::sync_attestation(
+            &self.oracle,
+            self.signature,
+            &self.attestation,
+            self.device.map(|device| (device.device, device.signature)),
+        )
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "challenge_id": self.challenge_id,
+            "user": self.user.to_string(),
+            "day": self.day,
+            "user_passed": self.attestation.user_passed,
+            "days_not_synced": self.attestation.days_not_synced,
+            "screen_time": self.attestation.report.screen_time,
+            "device": self.device.map(|device| device.device.to_string()),
+            "device_signature": self
+                .device
+                .map(|device| bs58::encode(device.signature).into_string()),
+            "oracle": self.oracle.to_string(),
+            "message": bs58::encode(self.message()).into_string(),
+            "signature": bs58::encode(self.signature).into_string(),
+        })
+    }
+}
+
+pub struct Attestor<S> {
+    oracle: Keypair,
+    challenges: S,
+    ledger: Ledger,
+}
+
+impl<S: ChallengeSource> Attestor<S> {
+    pub fn new(oracle: Keypair, challenges: S, ledger: Ledger) -> Self {
+        Self {
+            oracle,
+            challenges,
+            ledger,
+        }
+    }
+
+    pub fn oracle(&self) -> Pubkey {
+        self.oracle.pubkey()
+    }
+
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    // dev : a day passes when screen time stays within the challenge's daily_timer
+    pub fn verdict(report: &UsageReport, daily_timer: i64) -> bool {
+        i64::try_from(report.screen_time).is_ok_and(|screen_time| screen_time <= daily_timer)
+    }
+
+    // dev : a bad device signature would only fail on chain, reject it before it costs a ledger day
+    pub fn attest(&mut self, report: &UsageReport) -> Result<SignedAttestation> {
+        let daily_timer = self.challenges.daily_timer(report.challenge_id)?;
+        let device_report = report.device_report();
+
+        if report
+            .device
+            .is_some_and(|device| !device.verify(&device_report))
+        {
+            return Err(AttestorError::InvalidDeviceSignature {
+                challenge_id: report.challenge_id,
+                user: report.user,
+                day: report.day,
+            });
+        }
+
+        let next_sync_day = self
+            .challenges
+            .next_sync_day(report.challenge_id, &report.user)?;
+        let attestation = SyncAttestation {
+            user_passed: Self::verdict(report, daily_timer),
+            days_not_synced: self.ledger.days_not_synced(report, next_sync_day)?,
+            user: report.user.to_bytes(),
+            report: device_report,
+        };
+
+        let signature = self.oracle.sign_message(&attestation.to_bytes());
+        self.ledger.record(report, attestation.days_not_synced);
+
+        Ok(SignedAttestation {
+            challenge_id: report.challenge_id,
+            user: report.user,
+            day: report.day,
+            attestation,
+            oracle: self.oracle.pubkey(),
+            signature: signature.into(),
+            device: report.device,
+        })
+    }
+
+    // dev : reports are attested in day order per participant, upload order does not matter
+    pub fn attest_all(&mut self, reports: &[UsageReport]) -> Result<Vec<SignedAttestation>> {
+        let mut reports = reports.to_vec();
+        reports.sort_by_key(|report| (report.challenge_id, report.user, report.day));
+
+        reports.iter().map(|report| self.attest(report)).collect()
+    }
+}
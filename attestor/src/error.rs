@@ -0,0 +1,84 @@
+use {solana_pubkey::Pubkey, std::fmt};
+
+#[derive(Debug)]
+pub enum AttestorError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Key(String),
+    Config(String),
+    InvalidReport {
+        line: usize,
+        reason: String,
+    },
+    UnknownChallenge(u32),
+    AlreadyAttested {
+        challenge_id: u32,
+        user: Pubkey,
+        day: u32,
+    },
+    TooManyMissedDays {
+        challenge_id: u32,
+        user: Pubkey,
+        day: u32,
+    },
+    InvalidDeviceSignature {
+        challenge_id: u32,
+        user: Pubkey,
+        day: u32,
+    },
+    Submit(String),
+}
+
+pub type Result<T> = std::result::Result<T, AttestorError>;
+
+impl fmt::Display for AttestorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "io: {err}"),
+            Self::Json(err) => write!(f, "json: {err}"),
+            Self::Key(reason) => write!(f, "key store: {reason}"),
+            Self::Config(reason) => write!(f, "config: {reason}"),
+            Self::InvalidReport { line, reason } => write!(f, "report {line}: {reason}"),
+            Self::UnknownChallenge(id) => write!(f, "no daily timer known for challenge {id}"),
+            Self::AlreadyAttested {
+                challenge_id,
+                user,
+                day,
+            } => write!(
+                f,
+                "{user} already attested for day {day} of challenge {challenge_id}"
+            ),
+            Self::TooManyMissedDays {
+                challenge_id,
+                user,
+                day,
+            } => write!(
+                f,
+                "{user} missed more than 255 days before day {day} of challenge {challenge_id}"
+            ),
+            Self::InvalidDeviceSignature {
+                challenge_id,
+                user,
+                day,
+            } => write!(
+                f,
+                "{user} sent a report for day {day} of challenge {challenge_id} with a bad device signature"
+            ),
+            Self::Submit(reason) => write!(f, "submit: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for AttestorError {}
+
+impl From<std::io::Error> for AttestorError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for AttestorError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
@@ -0,0 +1,61 @@
+use {
+    crate::error::{AttestorError, Result},
+    solana_keypair::{read_keypair_file, write_keypair_file, Keypair},
+    std::path::{Path, PathBuf},
+};
+
+// dev : a directory holding the oracle keypair (solana cli json format) and the attestation ledger
+// the oracle pubkey is what the admin registers on-chain with set_oracle
+pub struct KeyStore {
+    dir: PathBuf,
+}
+
+impl KeyStore {
+    pub const ORACLE_KEY: &'static str = "oracle.json";
+    pub const LEDGER: &'static str = "ledger.json";
+
+    // dev : never overwrites an existing key, a lost oracle key means re-registering on-chain
+    pub fn create(dir: impl AsRef<Path>) -> Result<Self> {
+        let store = Self {
+            dir: dir.as_ref().to_path_buf(),
+        };
+        if store.oracle_path().exists() {
+            return Err(AttestorError::Key(format!(
+                "{} already exists",
+                store.oracle_path().display()
+            )));
+        }
+
+        std::fs::create_dir_all(&store.dir)?;
+        write_keypair_file(&Keypair::new(), store.oracle_path())
+            .map_err(|err| AttestorError::Key(err.to_string()))?;
+
+        Ok(store)
+    }
+
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let store = Self {
+            dir: dir.as_ref().to_path_buf(),
+        };
+        if !store.oracle_path().exists() {
+            return Err(AttestorError::Key(format!(
+                "{} not found, run keygen first",
+                store.oracle_path().display()
+            )));
+        }
+
+        Ok(store)
+    }
+
+    pub fn oracle(&self) -> Result<Keypair> {
+        read_keypair_file(self.oracle_path()).map_err(|err| AttestorError::Key(err.to_string()))
+    }
+
+    pub fn oracle_path(&self) -> PathBuf {
+        self.dir.join(Self::ORACLE_KEY)
+    }
+
+    pub fn ledger_path(&self) -> PathBuf {
+        self.dir.join(Self::LEDGER)
+    }
+}
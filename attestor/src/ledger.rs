@@ -0,0 +1,95 @@
+use {
+    crate::{
+        error::{AttestorError, Result},
+        report::UsageReport,
+    },
+    serde::{Deserialize, Serialize},
+    solana_pubkey::Pubkey,
+    std::{collections::BTreeMap, path::Path},
+};
+
+// dev : last attestation per participant, so a day is attested once and skipped days are counted
+// keys are `challenge_id:user`, json object keys have to be strings
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ledger {
+    attested: BTreeMap<String, Attested>,
+}
+
+// dev : an attestation covers the days from `from` (the next_sync_day it assumed) through `day`
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+struct Attested {
+    from: u32,
+    day: u32,
+}
+
+impl Ledger {
+    // dev : a missing file is an empty ledger, first run of a fresh store
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn last_day(&self, challenge_id: u32, user: &Pubkey) -> Option<u32> {
+        self.attested
+            .get(&Self::key(challenge_id, user))
+            .map(|attested| attested.day)
+    }
+
+    // dev : first day the attestation for `report` covers
+    // the on-chain next_sync_day wins when known, otherwise the ledger assumes every earlier
+    // attestation landed, except the last one which is signed again over the same days
+    // (it may never have been submitted)
+    pub fn next_day(&self, report: &UsageReport, next_sync_day: Option<u32>) -> u32 {
+        let attested = self
+            .attested
+            .get(&Self::key(report.challenge_id, &report.user));
+
+        match (next_sync_day, attested) {
+            (Some(next_sync_day), _) => next_sync_day,
+            (None, Some(attested)) if report.day == attested.day => attested.from,
+            (None, Some(attested)) => attested.day + 1,
+            (None, None) => 0,
+        }
+    }
+
+    // dev : days between the next unsynced day and this one, every day before the first report counts
+    pub fn days_not_synced(&self, report: &UsageReport, next_sync_day: Option<u32>) -> Result<u8> {
+        let next_day = self.next_day(report, next_sync_day);
+
+        if report.day < next_day {
+            return Err(AttestorError::AlreadyAttested {
+                challenge_id: report.challenge_id,
+                user: report.user,
+                day: report.day,
+            });
+        }
+
+        u8::try_from(report.day - next_day).map_err(|_| AttestorError::TooManyMissedDays {
+            challenge_id: report.challenge_id,
+            user: report.user,
+            day: report.day,
+        })
+    }
+
+    pub fn record(&mut self, report: &UsageReport, days_not_synced: u8) {
+        self.attested.insert(
+            Self::key(report.challenge_id, &report.user),
+            Attested {
+                from: report.day - days_not_synced as u32,
+                day: report.day,
+            },
+        );
+    }
+
+    fn key(challenge_id: u32, user: &Pubkey) -> String {
+        format!("{challenge_id}:{user}")
+    }
+}
@@ -0,0 +1,12 @@
+// dev : the offchain oracle component, turns device screen time reports into the ed25519
+// attestations SyncLock verifies against global.oracle
+
+pub mod attestor;
+pub mod error;
+pub mod keystore;
+pub mod ledger;
+pub mod report;
+#[cfg(feature = "litesvm")]
+pub mod testing;
+
+pub use {attestor::*, error::*, keystore::*, ledger::*, report::*};
@@ -0,0 +1,64 @@
+use {
+    screenwars_attestor::{
+        load_challenges, parse_reports, Attestor, AttestorError, KeyStore, Ledger, Result,
+    },
+    solana_signer::Signer,
+    std::process::ExitCode,
+};
+
+const USAGE: &str = "usage:
+  screenwars-attestor keygen <store>
+  screenwars-attestor pubkey <store>
+  screenwars-attestor attest <store> <challenges.json> <reports.json|reports.csv>";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args[..] {
+        ["keygen", store] => keygen(store),
+        ["pubkey", store] => pubkey(store),
+        ["attest", store, challenges, reports] => attest(store, challenges, reports),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn keygen(store: &str) -> Result<()> {
+    let store = KeyStore::create(store)?;
+    println!("{}", store.oracle()?.pubkey());
+    Ok(())
+}
+
+fn pubkey(store: &str) -> Result<()> {
+    println!("{}", KeyStore::open(store)?.oracle()?.pubkey());
+    Ok(())
+}
+
+// dev : one json attestation per line on stdout, the ledger is only saved once every report attested
+fn attest(store: &str, challenges: &str, reports: &str) -> Result<()> {
+    let store = KeyStore::open(store)?;
+    let reports = parse_reports(&std::fs::read_to_string(reports).map_err(AttestorError::Io)?)?;
+
+    let mut attestor = Attestor::new(
+        store.oracle()?,
+        load_challenges(challenges)?,
+        Ledger::load(store.ledger_path())?,
+    );
+
+    for attested in attestor.attest_all(&reports)? {
+        println!("{}", attested.to_json());
+    }
+
+    attestor.ledger().save(store.ledger_path())
+}
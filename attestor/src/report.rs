@@ -0,0 +1,164 @@
+use {
+    crate::error::{AttestorError, Result},
+    screenwars_pinocchio::{DeviceReport, SIGNATURE_LEN},
+    serde::Deserialize,
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    std::str::FromStr,
+};
+
+// dev : one participant's screen time on one challenge day, as uploaded from the device
+// json => [{"user": "<base58>", "challenge_id": 1, "day": 0, "screen_time": 3600}, ..]
+// csv  => header `user,challenge_id,day,screen_time` then one report per line
+// day is the 0 based day index since challenge start, screen_time is in seconds
+// participants with a registered device add "device" and "device_signature" (both base58),
+// in csv through the extended header `user,challenge_id,day,screen_time,device,device_signature`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UsageReport {
+    pub user: Pubkey,
+    pub challenge_id: u32,
+    pub day: u32,
+    pub screen_time: u64,
+    pub device: Option<DeviceSignature>,
+}
+
+// dev : the device key's signature over UsageReport::device_report()
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeviceSignature {
+    pub device: Pubkey,
+    pub signature: [u8; SIGNATURE_LEN],
+}
+
+impl UsageReport {
+    pub fn device_report(&self) -> DeviceReport {
+        DeviceReport {
+            challenge_id: self.challenge_id,
+            day: self.day,
+            screen_time: self.screen_time,
+        }
+    }
+}
+
+impl DeviceSignature {
+    pub fn verify(&self, report: &DeviceReport) -> bool {
+        Signature::from(self.signature).verify(self.device.as_ref(), &report.to_bytes())
+    }
+}
+
+pub const CSV_HEADER: &str = "user,challenge_id,day,screen_time";
+pub const CSV_DEVICE_HEADER: &str = "user,challenge_id,day,screen_time,device,device_signature";
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawReport {
+    user: String,
+    challenge_id: u32,
+    day: u32,
+    screen_time: u64,
+    #[serde(default)]
+    device: Option<String>,
+    #[serde(default)]
+    device_signature: Option<String>,
+}
+
+// dev : json when the input starts with `[`, csv otherwise
+pub fn parse_reports(input: &str) -> Result<Vec<UsageReport>> {
+    if input.trim_start().starts_with('[') {
+        parse_json(input)
+    } else {
+        parse_csv(input)
+    }
+}
+
+pub fn parse_json(input: &str) -> Result<Vec<UsageReport>> {
+    let raw: Vec<RawReport> = serde_json::from_str(input)?;
+
+    raw.into_iter()
+        .enumerate()
+        .map(|(index, report)| {
+            Ok(UsageReport {
+                user: parse_user(&report.user, index + 1)?,
+                challenge_id: report.challenge_id,
+                day: report.day,
+                screen_time: report.screen_time,
+                device: parse_device(
+                    report.device.as_deref().unwrap_or_default(),
+                    report.device_signature.as_deref().unwrap_or_default(),
+                    index + 1,
+                )?,
+            })
+        })
+        .collect()
+}
+
+pub fn parse_csv(input: &str) -> Result<Vec<UsageReport>> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let with_device = match lines.next() {
+        Some((_, header)) if header.replace(' ', "") == CSV_HEADER => false,
+        Some((_, header)) if header.replace(' ', "") == CSV_DEVICE_HEADER => true,
+        other => {
+            let line = other.map_or(1, |(line, _)| line);
+            return Err(invalid(line, &format!("expected header `{CSV_HEADER}`")));
+        }
+    };
+
+    lines
+        .map(|(line, row)| {
+            let mut fields: Vec<&str> = row.split(',').map(str::trim).collect();
+            let (device, device_signature) = match with_device {
+                true if fields.len() == 6 => (fields[4], fields[5]),
+                true => return Err(invalid(line, "expected 6 fields")),
+                false => ("", ""),
+            };
+            fields.truncate(4);
+            let [user, challenge_id, day, screen_time] = fields[..] else {
+                return Err(invalid(line, "expected 4 fields"));
+            };
+
+            Ok(UsageReport {
+                user: parse_user(user, line)?,
+                challenge_id: challenge_id
+                    .parse()
+                    .map_err(|_| invalid(line, "challenge_id is not a u32"))?,
+                day: day.parse().map_err(|_| invalid(line, "day is not a u32"))?,
+                screen_time: screen_time
+                    .parse()
+                    .map_err(|_| invalid(line, "screen_time is not a u64"))?,
+                device: parse_device(device, device_signature, line)?,
+            })
+        })
+        .collect()
+}
+
+fn parse_user(user: &str, line: usize) -> Result<Pubkey> {
+    Pubkey::from_str(user).map_err(|_| invalid(line, "user is not a base58 pubkey"))
+}
+
+// dev : both empty means the participant has no registered device
+fn parse_device(device: &str, signature: &str, line: usize) -> Result<Option<DeviceSignature>> {
+    if device.is_empty() && signature.is_empty() {
+        return Ok(None);
+    }
+
+    let device =
+        Pubkey::from_str(device).map_err(|_| invalid(line, "device is not a base58 pubkey"))?;
+    let signature = bs58::decode(signature)
+        .into_vec()
+        .ok()
+        .and_then(|signature| signature.try_into().ok())
+        .ok_or_else(|| invalid(line, "device_signature is not a base58 ed25519 signature"))?;
+
+    Ok(Some(DeviceSignature { device, signature }))
+}
+
+fn invalid(line: usize, reason: &str) -> AttestorError {
+    AttestorError::InvalidReport {
+        line,
+        reason: reason.to_string(),
+    }
+}
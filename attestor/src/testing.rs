@@ -0,0 +1,60 @@
+use {
+    crate::{
+        attestor::{ChallengeSource, SignedAttestation},
+        error::{AttestorError, Result},
+    },
+    litesvm::LiteSVM,
+    screenwars_pinocchio::client,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_transaction::Transaction,
+};
+
+// dev : test mode, daily timers and next_sync_day are read straight from the accounts in the LiteSVM
+pub struct LiteSvmChallenges<'a>(pub &'a LiteSVM);
+
+impl ChallengeSource for LiteSvmChallenges<'_> {
+    fn daily_timer(&self, challenge_id: u32) -> Result<i64> {
+        let account = self
+            .0
+            .get_account(&client::challenge_pda(challenge_id).0)
+            .ok_or(AttestorError::UnknownChallenge(challenge_id))?;
+        let challenge = client::decode_challenge(&account.data)
+            .map_err(|_| AttestorError::UnknownChallenge(challenge_id))?;
+
+        Ok(challenge.daily_timer)
+    }
+
+    fn next_sync_day(&self, challenge_id: u32, user: &Pubkey) -> Result<Option<u32>> {
+        let user_pda = self
+            .0
+            .get_account(&client::user_pda(user).0)
+            .and_then(|account| client::decode_user(&account.data).ok());
+
+        Ok(user_pda
+            .filter(|user_pda| { user_pda.challenge_id } == challenge_id)
+            .map(|user_pda| user_pda.next_sync_day))
+    }
+}
+
+// dev : sends [attestation, sync_lock] signed by the participant, returns the compute units used
+pub fn submit(
+    svm: &mut LiteSVM,
+    attested: &SignedAttestation,
+    participant: &Keypair,
+    team_id: u8,
+) -> Result<u64> {
+    let sync_lock = client::sync_lock(&participant.pubkey(), attested.challenge_id, team_id, None);
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[attested.instruction(), sync_lock],
+        Some(&participant.pubkey()),
+        &[participant],
+        svm.latest_blockhash(),
+    );
+
+    svm.send_transaction(transaction)
+        .map(|meta| meta.compute_units_consumed)
+        .map_err(|err| AttestorError::Submit(format!("{:?}", err.err)))
+}
@@ -0,0 +1,401 @@
+use {
+    litesvm::LiteSVM,
+    screenwars_attestor::{
+        parse_csv, parse_json, parse_reports, testing, Attestor, AttestorError, DeviceSignature,
+        KeyStore, Ledger, UsageReport,
+    },
+    screenwars_pinocchio::{
+        client, ed25519_signed_message, CreateChallengeInstructionData, DeviceReport,
+        JoinChallengeInstructionData, SyncAttestation, User,
+    },
+    solana_clock::Clock,
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    std::collections::HashMap,
+};
+
+const DAILY_TIMER: i64 = 60 * 60;
+
+fn report(user: Pubkey, day: u32, screen_time: u64) -> UsageReport {
+    UsageReport {
+        user,
+        challenge_id: 1,
+        day,
+        screen_time,
+        device: None,
+    }
+}
+
+fn device_signed(device: &Keypair, report: UsageReport) -> UsageReport {
+    let signature = device.sign_message(&report.device_report().to_bytes());
+
+    UsageReport {
+        device: Some(DeviceSignature {
+            device: device.pubkey(),
+            signature: signature.into(),
+        }),
+        ..report
+    }
+}
+
+fn attestor() -> Attestor<HashMap<u32, i64>> {
+    Attestor::new(
+        Keypair::new(),
+        HashMap::from([(1, DAILY_TIMER)]),
+        Ledger::default(),
+    )
+}
+
+#[test]
+pub fn test_report_formats() {
+    let user = Pubkey::new_unique();
+    let json = format!(
+        r#"[{{"user": "{user}", "challenge_id": 1, "day": 0, "screen_time": 3600}},
+            {{"user": "{user}", "challenge_id": 1, "day": 1, "screen_time": 7200}}]"#
+    );
+    let csv = format!("user,challenge_id,day,screen_time\n{user},1,0,3600\n\n{user}, 1, 1, 7200\n");
+
+    //// both formats describe the same reports
+    let expected = vec![report(user, 0, 3600), report(user, 1, 7200)];
+    assert!(parse_json(&json).unwrap() == expected);
+    assert!(parse_csv(&csv).unwrap() == expected);
+    assert!(parse_reports(&json).unwrap() == expected);
+    assert!(parse_reports(&csv).unwrap() == expected);
+
+    //// malformed reports name the offending line
+    let invalid_line = |input: &str| match parse_reports(input) {
+        Err(AttestorError::InvalidReport { line, .. }) => Some(line),
+        _ => None,
+    };
+    assert!(invalid_line(&format!("{user},1,0,3600")) == Some(1));
+    assert!(invalid_line(&format!("user,challenge_id,day,screen_time\n{user},1,0")) == Some(2));
+    assert!(invalid_line("user,challenge_id,day,screen_time\nnot-a-key,1,0,5") == Some(2));
+    assert!(invalid_line(&format!("user,challenge_id,day,screen_time\n{user},1,-1,5")) == Some(2));
+    assert!(matches!(
+        parse_reports(r#"[{"user": "x", "challenge_id": 1, "day": 0}]"#),
+        Err(AttestorError::Json(_))
+    ));
+}
+
+#[test]
+pub fn test_attestations() {
+    let mut attestor = attestor();
+    let user = Pubkey::new_unique();
+
+    //// screen time within daily_timer passes, anything above fails
+    let passed = attestor
+        .attest(&report(user, 0, DAILY_TIMER as u64))
+        .unwrap();
+    assert!(passed.attestation.user_passed);
+    assert!(passed.attestation.days_not_synced == 0);
+    let failed = attestor
+        .attest(&report(user, 1, DAILY_TIMER as u64 + 1))
+        .unwrap();
+    assert!(!failed.attestation.user_passed);
+
+    //// days without a report are counted, days before the last attested one are never attested again
+    let late = attestor.attest(&report(user, 4, 0)).unwrap();
+    assert!(late.attestation.days_not_synced == 2);
+
+    //// each attestation names its participant, challenge and day
+    assert!(late.attestation.user == user.to_bytes());
+    assert!(late.attestation.report.challenge_id == 1);
+    assert!(late.attestation.report.day == 4);
+
+    //// the last attestation may never have landed, so its day is signed again over the same days
+    let resigned = attestor.attest(&report(user, 4, 0)).unwrap();
+    assert!(resigned.attestation == late.attestation);
+    assert!(matches!(
+        attestor.attest(&report(user, 3, 0)),
+        Err(AttestorError::AlreadyAttested { day: 3, .. })
+    ));
+    assert!(matches!(
+        attestor.attest(&report(user, 2, 0)),
+        Err(AttestorError::AlreadyAttested { day: 2, .. })
+    ));
+    assert!(matches!(
+        attestor.attest(&report(user, 300, 0)),
+        Err(AttestorError::TooManyMissedDays { .. })
+    ));
+    assert!(matches!(
+        attestor.attest(&UsageReport {
+            challenge_id: 2,
+            ..report(user, 0, 0)
+        }),
+        Err(AttestorError::UnknownChallenge(2))
+    ));
+
+    //// batches are attested in day order whatever the upload order
+    let other = Pubkey::new_unique();
+    let attested = attestor
+        .attest_all(&[report(other, 2, 0), report(other, 0, 0)])
+        .unwrap();
+    assert!(attested.iter().map(|a| a.day).collect::<Vec<_>>() == [0, 2]);
+    assert!(attested[1].attestation.days_not_synced == 1);
+
+    //// the on-chain next_sync_day overrides the ledger, days it already synced are refused
+    let ledger = attestor.ledger();
+    assert!(
+        ledger
+            .days_not_synced(&report(user, 4, 0), Some(3))
+            .unwrap()
+            == 1
+    );
+    assert!(
+        ledger
+            .days_not_synced(&report(user, 6, 0), Some(3))
+            .unwrap()
+            == 3
+    );
+    assert!(matches!(
+        ledger.days_not_synced(&report(user, 4, 0), Some(5)),
+        Err(AttestorError::AlreadyAttested { day: 4, .. })
+    ));
+}
+
+#[test]
+pub fn test_attestation_bytes() {
+    let oracle = Keypair::new();
+    let mut attestor = Attestor::new(
+        oracle.insecure_clone(),
+        HashMap::from([(1, DAILY_TIMER)]),
+        Ledger::default(),
+    );
+    let attested = attestor
+        .attest(&report(Pubkey::new_unique(), 0, 10))
+        .unwrap();
+
+    //// the signature covers exactly the SyncAttestation bytes
+    assert!(attested.message() == attested.attestation.to_bytes());
+    let signature: [u8; 64] = oracle.sign_message(&attested.message()).into();
+    assert!(attested.signature == signature);
+
+    //// and the instruction is what SyncLock reads back for global.oracle
+    let instruction: Instruction = attested.instruction();
+    assert!(instruction.program_id == client::ED25519_PROGRAM_ID);
+    let message = ed25519_signed_message(&instruction.data, &oracle.pubkey().to_bytes()).unwrap();
+    assert!(SyncAttestation::from_bytes(message) == Ok(attested.attestation));
+
+    let json = attested.to_json();
+    assert!(json["oracle"] == oracle.pubkey().to_string());
+    assert!(json["signature"] == bs58::encode(signature).into_string());
+}
+
+#[test]
+pub fn test_device_reports() {
+    let user = Pubkey::new_unique();
+    let device = Keypair::new();
+    let signed = device_signed(&device, report(user, 0, 3600));
+    let signature = bs58::encode(signed.device.unwrap().signature).into_string();
+
+    //// the device columns are optional in both formats
+    let json = format!(
+        r#"[{{"user": "{user}", "challenge_id": 1, "day": 0, "screen_time": 3600,
+              "device": "{}", "device_signature": "{signature}"}},
+            {{"user": "{user}", "challenge_id": 1, "day": 1, "screen_time": 7200}}]"#,
+        device.pubkey()
+    );
+    let csv = format!(
+        "user,challenge_id,day,screen_time,device,device_signature\n{user},1,0,3600,{},{signature}\n{user},1,1,7200,,\n",
+        device.pubkey()
+    );
+    let expected = vec![signed.clone(), report(user, 1, 7200)];
+    assert!(parse_json(&json).unwrap() == expected);
+    assert!(parse_csv(&csv).unwrap() == expected);
+
+    let invalid_line = |input: &str| match parse_reports(input) {
+        Err(AttestorError::InvalidReport { line, .. }) => Some(line),
+        _ => None,
+    };
+    let header = "user,challenge_id,day,screen_time,device,device_signature";
+    assert!(invalid_line(&format!("{header}\n{user},1,0,5")) == Some(2));
+    assert!(invalid_line(&format!("{header}\n{user},1,0,5,{user},")) == Some(2));
+    assert!(invalid_line(&format!("{header}\n{user},1,0,5,{user},abc")) == Some(2));
+
+    //// the device signature is checked before the oracle signs anything
+    let mut attestor = attestor();
+    let tampered = UsageReport {
+        screen_time: 60,
+        ..signed.clone()
+    };
+    assert!(matches!(
+        attestor.attest(&tampered),
+        Err(AttestorError::InvalidDeviceSignature { day: 0, .. })
+    ));
+    assert!(attestor.ledger().last_day(1, &user).is_none());
+
+    //// and travels in the same ed25519 instruction as the oracle's
+    let attested = attestor.attest(&signed).unwrap();
+    assert!(attested.attestation.report == signed.device_report());
+    let instruction = attested.instruction();
+    let message = ed25519_signed_message(&instruction.data, &device.pubkey().to_bytes()).unwrap();
+    assert!(DeviceReport::from_bytes(message) == Ok(signed.device_report()));
+    assert!(attested.to_json()["device_signature"] == signature);
+}
+
+#[test]
+pub fn test_key_store() {
+    let dir = std::env::temp_dir().join(format!("screenwars-attestor-{}", Pubkey::new_unique()));
+
+    assert!(matches!(KeyStore::open(&dir), Err(AttestorError::Key(_))));
+    let store = KeyStore::create(&dir).unwrap();
+    let oracle = store.oracle().unwrap().pubkey();
+
+    //// keys are kept across runs and never overwritten
+    assert!(KeyStore::open(&dir).unwrap().oracle().unwrap().pubkey() == oracle);
+    assert!(matches!(KeyStore::create(&dir), Err(AttestorError::Key(_))));
+
+    //// so is the ledger
+    let user = Pubkey::new_unique();
+    let mut ledger = Ledger::load(store.ledger_path()).unwrap();
+    assert!(ledger == Ledger::default());
+    ledger.record(&report(user, 3, 0), 1);
+    ledger.save(store.ledger_path()).unwrap();
+    assert!(
+        Ledger::load(store.ledger_path())
+            .unwrap()
+            .last_day(1, &user)
+            == Some(3)
+    );
+    assert!(
+        Ledger::load(store.ledger_path())
+            .unwrap()
+            .next_day(&report(user, 3, 0), None)
+            == 2
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+pub fn test_litesvm_sync() {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(
+        client::program_id(),
+        "../target/deploy/screenwars_pinocchio.so",
+    )
+    .unwrap();
+
+    let admin = Keypair::new();
+    let creator = Keypair::new();
+    let participant = Keypair::new();
+    let oracle = Keypair::new();
+    let device = Keypair::new();
+    for wallet in [&admin, &creator, &participant] {
+        svm.airdrop(&wallet.pubkey(), 1_000_000_000).unwrap();
+    }
+
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = 1_735_689_600;
+    svm.set_sysvar(&clock);
+    let start = clock.unix_timestamp + 24 * 60 * 60 + 1;
+
+    let send = |svm: &mut LiteSVM, instructions: &[Instruction], signer: &Keypair| {
+        let transaction = solana_transaction::Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &[signer],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(transaction).unwrap();
+    };
+
+    send(
+        &mut svm,
+        &[
+            client::initialize(&admin.pubkey()),
+            client::set_oracle(&admin.pubkey(), &oracle.pubkey()),
+        ],
+        &admin,
+    );
+    send(
+        &mut svm,
+        &[client::create_challenge(
+            &creator.pubkey(),
+            1,
+            CreateChallengeInstructionData {
+                start_time: start,
+                daily_timer: DAILY_TIMER,
+                allowlist_root: None,
+                token_gate: None,
+                duel: None,
+                team_count: None,
+                extensions: &[],
+            },
+        )],
+        &creator,
+    );
+    send(
+        &mut svm,
+        &[client::join_challenge(
+            &participant.pubkey(),
+            None,
+            JoinChallengeInstructionData {
+                challenge_id: 1,
+                team_id: 0,
+                prepay: false,
+                device: device.pubkey().to_bytes(),
+                allowlist_proof: &[],
+            },
+        )],
+        &participant,
+    );
+
+    clock.unix_timestamp = start + 1;
+    svm.set_sysvar(&clock);
+
+    //// timers come from the challenge account, the attestation is accepted by SyncLock
+    let attested = {
+        let challenges = testing::LiteSvmChallenges(&svm);
+        let mut attestor = Attestor::new(oracle.insecure_clone(), challenges, Ledger::default());
+        let report = report(participant.pubkey(), 0, DAILY_TIMER as u64 - 1);
+        attestor.attest(&device_signed(&device, report)).unwrap()
+    };
+    testing::submit(&mut svm, &attested, &participant, 0).unwrap();
+
+    let user_pda = svm
+        .get_account(&client::user_pda(&participant.pubkey()).0)
+        .unwrap();
+    assert!(User::read(&user_pda.data).unwrap().streak == 1);
+
+    //// the same attestation is not accepted twice
+    svm.expire_blockhash();
+    assert!(testing::submit(&mut svm, &attested, &participant, 0).is_err());
+
+    //// a key that is not global.oracle is refused
+    let stranger = Attestor::new(
+        Keypair::new(),
+        HashMap::from([(1, DAILY_TIMER)]),
+        Ledger::default(),
+    )
+    .attest(&device_signed(&device, report(participant.pubkey(), 1, 0)));
+    assert!(testing::submit(&mut svm, &stranger.unwrap(), &participant, 0).is_err());
+
+    //// an attestation that never landed is signed again, counted from the on-chain next_sync_day
+    clock.unix_timestamp = start + 24 * 60 * 60 + 1;
+    svm.set_sysvar(&clock);
+
+    let mut ledger = Ledger::default();
+    let mut attest = |svm: &LiteSVM, day| {
+        let challenges = testing::LiteSvmChallenges(svm);
+        let mut attestor = Attestor::new(oracle.insecure_clone(), challenges, ledger.clone());
+        let attested = attestor.attest(&device_signed(
+            &device,
+            report(participant.pubkey(), day, 0),
+        ));
+        ledger = attestor.ledger().clone();
+        attested
+    };
+    let lost = attest(&svm, 1).unwrap();
+    let resigned = attest(&svm, 1).unwrap();
+    assert!(resigned.attestation == lost.attestation);
+    assert!(resigned.attestation.days_not_synced == 0);
+    testing::submit(&mut svm, &resigned, &participant, 0).unwrap();
+
+    assert!(matches!(
+        attest(&svm, 1),
+        Err(AttestorError::AlreadyAttested { day: 1, .. })
+    ));
+}
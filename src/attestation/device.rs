@@ -0,0 +1,59 @@
+use {
+    crate::{utils::Decoder, ScreenWarErrors},
+    pinocchio::program_error::ProgramError,
+};
+
+// dev : one day of screen time as measured on the participant's phone, signed by the device key
+// registered at join, the oracle attests over the same report so the two can be compared
+// message => [DOMAIN.., challenge_id, day, screen_time]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DeviceReport {
+    pub challenge_id: u32,
+    pub day: u32,
+    pub screen_time: u64,
+}
+
+impl DeviceReport {
+    pub const DOMAIN: &'static [u8] = b"screenwars:report";
+    pub const FIELDS_LEN: usize = 4 + 4 + 8;
+    pub const LEN: usize = Self::DOMAIN.len() + Self::FIELDS_LEN;
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        let (domain, fields) = bytes.split_at_mut(Self::DOMAIN.len());
+        domain.copy_from_slice(Self::DOMAIN);
+        fields.copy_from_slice(&self.fields());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProgramError> {
+        let malformed = |_| ProgramError::from(ScreenWarErrors::InvalidAttestation);
+
+        let mut message = Decoder::new(bytes);
+        if message.take(Self::DOMAIN.len()).map_err(malformed)? != Self::DOMAIN {
+            return Err(ScreenWarErrors::InvalidAttestation.into());
+        }
+
+        let report = Self::read_fields(&mut message).map_err(malformed)?;
+        message.finish().map_err(malformed)?;
+
+        Ok(report)
+    }
+
+    // dev : the report without its domain, as embedded in SyncAttestation
+    pub fn fields(&self) -> [u8; Self::FIELDS_LEN] {
+        let mut fields = [0u8; Self::FIELDS_LEN];
+        fields[..4].copy_from_slice(&self.challenge_id.to_le_bytes());
+        fields[4..8].copy_from_slice(&self.day.to_le_bytes());
+        fields[8..].copy_from_slice(&self.screen_time.to_le_bytes());
+        fields
+    }
+
+    pub fn read_fields(message: &mut Decoder) -> Result<Self, ProgramError> {
+        Ok(Self {
+            challenge_id: message.u32()?,
+            day: message.u32()?,
+            screen_time: message.u64()?,
+        })
+    }
+}
@@ -0,0 +1,59 @@
+use {
+    crate::{utils::Decoder, ScreenWarErrors},
+    core::mem::size_of,
+    pinocchio::{program_error::ProgramError, pubkey::Pubkey},
+};
+
+pub const ED25519_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+// dev : ed25519 program instruction data
+// [num_signatures, padding, offsets (14 bytes each).., pubkeys, signatures and messages..]
+// offsets : [signature, signature_ix, pubkey, pubkey_ix, message, message_len, message_ix] as u16
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+pub const SIGNATURE_OFFSETS_LEN: usize = 14;
+pub const SIGNATURE_LEN: usize = 64;
+
+// dev : instruction index the precompile reads as "my own data"
+pub const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+// dev : returns the message `signer` signed inside one ed25519 program instruction
+// the precompile verified every signature before this program runs, but only against the instructions
+// the offsets point to, so anything pointing outside its own data is rejected
+pub fn ed25519_signed_message<'a>(
+    data: &'a [u8],
+    signer: &Pubkey,
+) -> Result<&'a [u8], ProgramError> {
+    let malformed = |_| ProgramError::from(ScreenWarErrors::InvalidAttestation);
+
+    let mut offsets = Decoder::new(data);
+    let num_signatures = offsets.u8().map_err(malformed)?;
+    offsets.u8().map_err(malformed)?;
+
+    for _ in 0..num_signatures {
+        let mut next = || offsets.u16().map_err(malformed);
+        let (_, signature_ix) = (next()?, next()?);
+        let (pubkey, pubkey_ix) = (next()?, next()?);
+        let (message, message_len, message_ix) = (next()?, next()?, next()?);
+
+        if [signature_ix, pubkey_ix, message_ix]
+            .iter()
+            .any(|index| *index != CURRENT_INSTRUCTION)
+        {
+            return Err(ScreenWarErrors::InvalidAttestation.into());
+        }
+
+        let key = slice(data, pubkey, size_of::<Pubkey>())?;
+        if key.eq(signer) {
+            return slice(data, message, message_len as usize);
+        }
+    }
+
+    Err(ScreenWarErrors::MissingAttestation.into())
+}
+
+fn slice(data: &[u8], offset: u16, len: usize) -> Result<&[u8], ProgramError> {
+    let start = offset as usize;
+    data.get(start..start + len)
+        .ok_or(ScreenWarErrors::InvalidAttestation.into())
+}
@@ -0,0 +1,5 @@
+pub mod device;
+pub mod ed25519;
+pub mod sync;
+
+pub use {device::*, ed25519::*, sync::*};
@@ -0,0 +1,127 @@
+use {
+    crate::{
+        attestation::{ed25519_signed_message, DeviceReport, ED25519_PROGRAM_ID},
+        state::User,
+        utils::Decoder,
+        ScreenWarErrors,
+    },
+    core::mem::size_of,
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::instructions::{Instructions, INSTRUCTIONS_ID},
+        ProgramResult,
+    },
+};
+
+// dev : the oracle's verdict on one participant's day, signed off-chain and checked by SyncLock
+// message => [DOMAIN.., user_passed, days_not_synced, user.., report fields..]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SyncAttestation {
+    pub user_passed: bool,
+    pub days_not_synced: u8,
+    pub user: Pubkey, // dev : participant wallet, with report.challenge_id and report.day pins the verdict to one sync
+    pub report: DeviceReport, // dev : the report the verdict was made on, must match the device signed one
+}
+
+impl SyncAttestation {
+    // dev : keeps an oracle signature over anything else from ever reading as a sync verdict
+    pub const DOMAIN: &'static [u8] = b"screenwars:sync";
+    pub const LEN: usize = Self::DOMAIN.len() + 2 + size_of::<Pubkey>() + DeviceReport::FIELDS_LEN;
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut bytes = [0u8; Self::LEN];
+        let (domain, rest) = bytes.split_at_mut(Self::DOMAIN.len());
+        let (verdict, rest) = rest.split_at_mut(2);
+        let (user, report) = rest.split_at_mut(size_of::<Pubkey>());
+        domain.copy_from_slice(Self::DOMAIN);
+        verdict.copy_from_slice(&[self.user_passed as u8, self.days_not_synced]);
+        user.copy_from_slice(&self.user);
+        report.copy_from_slice(&self.report.fields());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProgramError> {
+        let malformed = |_| ProgramError::from(ScreenWarErrors::InvalidAttestation);
+
+        let mut message = Decoder::new(bytes);
+        if message.take(Self::DOMAIN.len()).map_err(malformed)? != Self::DOMAIN {
+            return Err(ScreenWarErrors::InvalidAttestation.into());
+        }
+
+        let attestation = Self {
+            user_passed: message.bool().map_err(malformed)?,
+            days_not_synced: message.u8().map_err(malformed)?,
+            user: message.pubkey().map_err(malformed)?,
+            report: DeviceReport::read_fields(&mut message).map_err(malformed)?,
+        };
+        message.finish().map_err(malformed)?;
+
+        Ok(attestation)
+    }
+
+    // dev : the attestation travels as the ed25519 program instruction right before SyncLock
+    // a participant with a registered device also needs the device signed report in that same instruction
+    pub fn verify(
+        instructions_sysvar: &AccountInfo,
+        oracle: &Pubkey,
+        device: &Pubkey,
+    ) -> Result<Self, ProgramError> {
+        if oracle.eq(&Pubkey::default()) {
+            return Err(ScreenWarErrors::NotOracle.into());
+        }
+
+        if instructions_sysvar.key().ne(&INSTRUCTIONS_ID) {
+            return Err(ScreenWarErrors::InvalidAccountAddress.into());
+        }
+
+        let instructions = Instructions::try_from(instructions_sysvar)?;
+        let previous = instructions
+            .get_instruction_relative(-1)
+            .map_err(|_| ScreenWarErrors::MissingAttestation)?;
+
+        if previous.get_program_id().ne(&ED25519_PROGRAM_ID) {
+            return Err(ScreenWarErrors::MissingAttestation.into());
+        }
+
+        let data = previous.get_instruction_data();
+        let attestation = Self::from_bytes(ed25519_signed_message(data, oracle)?)?;
+
+        if device.ne(&Pubkey::default()) {
+            let signed = ed25519_signed_message(data, device).map_err(|err| {
+                if err == ScreenWarErrors::MissingAttestation.into() {
+                    ScreenWarErrors::MissingDeviceSignature.into()
+                } else {
+                    err
+                }
+            })?;
+
+            if DeviceReport::from_bytes(signed)?.ne(&attestation.report) {
+                return Err(ScreenWarErrors::DeviceReportMismatch.into());
+            }
+        }
+
+        Ok(attestation)
+    }
+
+    // dev : a verdict only counts once, for its own participant and challenge
+    // missed days are charged by the sync that reports after them, so the attested day comes
+    // days_not_synced after the next unsynced one, and never lies ahead of today
+    pub fn validate_sync(&self, user_pda: &User, challenge_id: u32, today: u32) -> ProgramResult {
+        if self.user.ne(&user_pda.user) || self.report.challenge_id.ne(&challenge_id) {
+            return Err(ScreenWarErrors::AttestationMismatch.into());
+        }
+
+        let expected_day = user_pda
+            .next_sync_day
+            .checked_add(self.days_not_synced as u32)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        if self.report.day.ne(&expected_day) || self.report.day > today {
+            return Err(ScreenWarErrors::InvalidSyncDay.into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use {
+    crate::state::{Challenge, Excusal, Extension, Global, Team, User},
+    pinocchio::program_error::ProgramError,
+};
+
+// dev : decoders return owned copies so callers can drop the fetched account bytes
+// accounts not yet migrated decode too, upgraded to the current layout
+
+pub fn decode_global(data: &[u8]) -> Result<Global, ProgramError> {
+    Global::read(data)
+}
+
+pub fn decode_challenge(data: &[u8]) -> Result<Challenge, ProgramError> {
+    Challenge::read(data)
+}
+
+// dev : accounts from before extensions existed simply have none
+pub fn decode_challenge_extension<T: Extension>(data: &[u8]) -> Result<Option<T>, ProgramError> {
+    if data.len() == Challenge::V0_LEN {
+        return Ok(None);
+    }
+
+    Challenge::extension(data)
+}
+
+pub fn decode_user(data: &[u8]) -> Result<User, ProgramError> {
+    User::read(data)
+}
+
+pub fn decode_team(data: &[u8]) -> Result<Team, ProgramError> {
+    Team::read(data)
+}
+
+pub fn decode_excusal(data: &[u8]) -> Result<Excusal, ProgramError> {
+    Excusal::read(data)
+}
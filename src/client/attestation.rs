@@ -0,0 +1,68 @@
+use {
+    crate::{
+        attestation::{
+            SyncAttestation, CURRENT_INSTRUCTION, SIGNATURE_LEN, SIGNATURE_OFFSETS_LEN,
+            SIGNATURE_OFFSETS_START,
+        },
+        client::pda::ED25519_PROGRAM_ID,
+    },
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    std::{vec, vec::Vec},
+};
+
+// dev : ed25519 program instruction carrying already signed messages, every offset points into itself
+// signing stays with the caller, the sdk has no keys
+pub fn ed25519_verify(signed: &[(Pubkey, [u8; SIGNATURE_LEN], &[u8])]) -> Instruction {
+    let header_len = SIGNATURE_OFFSETS_START + signed.len() * SIGNATURE_OFFSETS_LEN;
+    let mut data = vec![signed.len() as u8, 0];
+    let mut payload: Vec<u8> = Vec::new();
+
+    for (pubkey, signature, message) in signed {
+        let pubkey_offset = header_len + payload.len();
+        payload.extend_from_slice(pubkey.as_ref());
+        let signature_offset = header_len + payload.len();
+        payload.extend_from_slice(signature);
+        let message_offset = header_len + payload.len();
+        payload.extend_from_slice(message);
+
+        for offset in [
+            signature_offset,
+            CURRENT_INSTRUCTION as usize,
+            pubkey_offset,
+            CURRENT_INSTRUCTION as usize,
+            message_offset,
+            message.len(),
+            CURRENT_INSTRUCTION as usize,
+        ] {
+            data.extend_from_slice(&(offset as u16).to_le_bytes());
+        }
+    }
+    data.extend_from_slice(&payload);
+
+    Instruction {
+        program_id: ED25519_PROGRAM_ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+// dev : goes right before sync_lock() / delegated_sync_lock() in the same transaction
+// device is the registered device key and its signature over attestation.report, when one was bound at join
+pub fn sync_attestation(
+    oracle: &Pubkey,
+    signature: [u8; SIGNATURE_LEN],
+    attestation: &SyncAttestation,
+    device: Option<(Pubkey, [u8; SIGNATURE_LEN])>,
+) -> Instruction {
+    let message = attestation.to_bytes();
+    let report = attestation.report.to_bytes();
+
+    match device {
+        Some((device, device_signature)) => ed25519_verify(&[
+            (*oracle, signature, &message),
+            (device, device_signature, &report),
+        ]),
+        None => ed25519_verify(&[(*oracle, signature, &message)]),
+    }
+}
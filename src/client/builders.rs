@@ -0,0 +1,450 @@
+use {
+    crate::{
+        client::pda::{
+            challenge_pda, excusal_pda, global_pda, program_id, team_pda, user_pda,
+            CLOCK_SYSVAR_ID, INSTRUCTIONS_SYSVAR_ID, RENT_SYSVAR_ID, SYSTEM_PROGRAM_ID,
+        },
+        instructions::{
+            BatchSyncInstructionData, ClaimRewardsInstructionData,
+            ClaimWinnerPositionInstructionData, CreateChallengeInstructionData, DebugData,
+            ExcuseDaysInstructionData, JoinChallengeInstructionData, ScreenWarsInstruction,
+            SetOracleInstructionData, SetSyncDelegateInstructionData, SettleDuelInstructionData,
+            SyncLockInstructionData, TakeProfitInstructionData,
+            ToggleChallengeCreationInstructionData, UpdateAllowlistInstructionData,
+            ViewInstructionData, WithdrawInstructionData,
+        },
+    },
+    solana_instruction::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    std::{vec, vec::Vec},
+};
+
+fn build(accounts: Vec<AccountMeta>, instruction: ScreenWarsInstruction) -> Instruction {
+    let mut data = vec![0u8; instruction.packed_len()];
+    instruction
+        .pack(&mut data)
+        .expect("buffer is sized by packed_len");
+
+    Instruction {
+        program_id: program_id(),
+        accounts,
+        data,
+    }
+}
+
+pub fn initialize(admin: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(global_pda().0, false),
+        AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+
+    build(accounts, ScreenWarsInstruction::Initialize)
+}
+
+// dev : challenge_id must equal the current global.challenge_ids
+pub fn create_challenge(
+    creator: &Pubkey,
+    challenge_id: u32,
+    data: CreateChallengeInstructionData,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*creator, true),
+        AccountMeta::new(global_pda().0, false),
+        AccountMeta::new(challenge_pda(challenge_id).0, false),
+        AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+
+    build(accounts, ScreenWarsInstruction::CreateChallenge(data))
+}
+
+// dev : gate_token_account is required only for token gated challenges
+pub fn join_challenge(
+    user: &Pubkey,
+    gate_token_account: Option<&Pubkey>,
+    data: JoinChallengeInstructionData,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new(challenge_pda(data.challenge_id).0, false),
+        AccountMeta::new(user_pda(user).0, false),
+        AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+    if let Some(token_account) = gate_token_account {
+        accounts.push(AccountMeta::new_readonly(*token_account, false));
+    }
+    if data.team_id > 0 {
+        accounts.push(AccountMeta::new(
+            team_pda(data.challenge_id, data.team_id).0,
+            false,
+        ));
+    }
+
+    build(accounts, ScreenWarsInstruction::JoinChallenge(data))
+}
+
+fn sync_lock_accounts(
+    user: &Pubkey,
+    user_signs: bool,
+    challenge_id: u32,
+    team_id: u8,
+) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new(*user, user_signs),
+        AccountMeta::new(global_pda().0, false),
+        AccountMeta::new(challenge_pda(challenge_id).0, false),
+        AccountMeta::new(user_pda(user).0, false),
+        AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(INSTRUCTIONS_SYSVAR_ID, false),
+    ];
+    if team_id > 0 {
+        accounts.push(AccountMeta::new(team_pda(challenge_id, team_id).0, false));
+    }
+
+    accounts
+}
+
+// dev : team_id is 0 for individual challenges
+pub fn sync_lock(
+    user: &Pubkey,
+    challenge_id: u32,
+    team_id: u8,
+    debug_data: Option<DebugData>,
+) -> Instruction {
+    let accounts = sync_lock_accounts(user, true, challenge_id, team_id);
+
+    build(
+        accounts,
+        ScreenWarsInstruction::SyncLock(SyncLockInstructionData {
+            challenge_id,
+            debug_data,
+        }),
+    )
+}
+
+// dev : signed by the session key registered with set_sync_delegate instead of the user
+pub fn delegated_sync_lock(
+    user: &Pubkey,
+    delegate: &Pubkey,
+    challenge_id: u32,
+    team_id: u8,
+    debug_data: Option<DebugData>,
+) -> Instruction {
+    let mut accounts = sync_lock_accounts(user, false, challenge_id, team_id);
+    accounts.push(AccountMeta::new(*delegate, true));
+
+    build(
+        accounts,
+        ScreenWarsInstruction::SyncLock(SyncLockInstructionData {
+            challenge_id,
+            debug_data,
+        }),
+    )
+}
+
+// dev : appends the excusal pdas of the given scopes to a sync_lock, delegated_sync_lock or batch_sync
+pub fn with_excusals(mut instruction: Instruction, scopes: &[u32]) -> Instruction {
+    for scope in scopes {
+        instruction
+            .accounts
+            .push(AccountMeta::new_readonly(excusal_pda(*scope).0, false));
+    }
+
+    instruction
+}
+
+pub fn claim_winner_position(user: &Pubkey, challenge_id: u32, team_id: u8) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new(challenge_pda(challenge_id).0, false),
+        AccountMeta::new_readonly(user_pda(user).0, false),
+        AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+    ];
+    if team_id > 0 {
+        accounts.push(AccountMeta::new_readonly(
+            team_pda(challenge_id, team_id).0,
+            false,
+        ));
+    }
+
+    build(
+        accounts,
+        ScreenWarsInstruction::ClaimWinnerPosition(ClaimWinnerPositionInstructionData {
+            challenge_id,
+        }),
+    )
+}
+
+pub fn withdraw(user: &Pubkey, challenge_id: u32) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new(global_pda().0, false),
+        AccountMeta::new(challenge_pda(challenge_id).0, false),
+        AccountMeta::new(user_pda(user).0, false),
+        AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::Withdraw(WithdrawInstructionData { challenge_id }),
+    )
+}
+
+fn claim_rewards_accounts(user: &Pubkey, challenge_id: u32) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new(global_pda().0, false),
+        AccountMeta::new(challenge_pda(challenge_id).0, false),
+        AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ]
+}
+
+pub fn claim_winner_rewards(winner: &Pubkey, challenge_id: u32) -> Instruction {
+    build(
+        claim_rewards_accounts(winner, challenge_id),
+        ScreenWarsInstruction::ClaimWinnerRewards(ClaimRewardsInstructionData { challenge_id }),
+    )
+}
+
+pub fn claim_creator_rewards(creator: &Pubkey, challenge_id: u32) -> Instruction {
+    build(
+        claim_rewards_accounts(creator, challenge_id),
+        ScreenWarsInstruction::ClaimCreatorRewards(ClaimRewardsInstructionData { challenge_id }),
+    )
+}
+
+pub fn claim_team_rewards(member: &Pubkey, challenge_id: u32, winning_team: u8) -> Instruction {
+    let mut accounts = claim_rewards_accounts(member, challenge_id);
+    accounts.push(AccountMeta::new(user_pda(member).0, false));
+    accounts.push(AccountMeta::new_readonly(
+        team_pda(challenge_id, winning_team).0,
+        false,
+    ));
+
+    build(
+        accounts,
+        ScreenWarsInstruction::ClaimTeamRewards(ClaimRewardsInstructionData { challenge_id }),
+    )
+}
+
+pub fn take_profit(admin: &Pubkey, amount: u64) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new(global_pda().0, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::TakeProfit(TakeProfitInstructionData { amount }),
+    )
+}
+
+pub fn toggle_challenge_creation(admin: &Pubkey, pause: bool) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(global_pda().0, false),
+    ];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::ToggleChallengeCreation(ToggleChallengeCreationInstructionData {
+            pause,
+        }),
+    )
+}
+
+pub fn update_allowlist(
+    creator: &Pubkey,
+    challenge_id: u32,
+    allowlist_root: [u8; 32],
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*creator, true),
+        AccountMeta::new(challenge_pda(challenge_id).0, false),
+    ];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::UpdateAllowlist(UpdateAllowlistInstructionData {
+            challenge_id,
+            allowlist_root,
+        }),
+    )
+}
+
+// dev : permissionless, any caller can settle once the duel has ended
+pub fn settle_duel(
+    caller: &Pubkey,
+    challenge_id: u32,
+    creator: &Pubkey,
+    opponent: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*caller, true),
+        AccountMeta::new(challenge_pda(challenge_id).0, false),
+        AccountMeta::new(*creator, false),
+        AccountMeta::new(*opponent, false),
+        AccountMeta::new_readonly(user_pda(creator).0, false),
+        AccountMeta::new_readonly(user_pda(opponent).0, false),
+        AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+    ];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::SettleDuel(SettleDuelInstructionData { challenge_id }),
+    )
+}
+
+// dev : Pubkey::default() as delegate revokes the current session key
+pub fn set_sync_delegate(user: &Pubkey, delegate: &Pubkey, delegate_expiry: i64) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*user, true),
+        AccountMeta::new(user_pda(user).0, false),
+        AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+    ];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::SetSyncDelegate(SetSyncDelegateInstructionData {
+            delegate: delegate.to_bytes(),
+            delegate_expiry,
+        }),
+    )
+}
+
+pub fn set_oracle(admin: &Pubkey, oracle: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(global_pda().0, false),
+    ];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::SetOracle(SetOracleInstructionData {
+            oracle: oracle.to_bytes(),
+        }),
+    )
+}
+
+// dev : authority is the admin or the oracle, challenge_id 0 excuses the range in every challenge
+pub fn excuse_days(authority: &Pubkey, challenge_id: u32, start: i64, end: i64) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(global_pda().0, false),
+        AccountMeta::new(excusal_pda(challenge_id).0, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::ExcuseDays(ExcuseDaysInstructionData {
+            challenge_id,
+            start,
+            end,
+        }),
+    )
+}
+
+// dev : results are (user, user_passed, days_not_synced)
+pub fn batch_sync(
+    oracle: &Pubkey,
+    challenge_id: u32,
+    results: &[(Pubkey, bool, u8)],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*oracle, true),
+        AccountMeta::new(global_pda().0, false),
+        AccountMeta::new(challenge_pda(challenge_id).0, false),
+        AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+    ];
+
+    let mut packed_results = Vec::with_capacity(results.len() * 2);
+    for (user, user_passed, days_not_synced) in results {
+        accounts.push(AccountMeta::new(user_pda(user).0, false));
+        packed_results.extend_from_slice(&[*user_passed as u8, *days_not_synced]);
+    }
+
+    build(
+        accounts,
+        ScreenWarsInstruction::BatchSync(BatchSyncInstructionData {
+            challenge_id,
+            results: &packed_results,
+        }),
+    )
+}
+
+// dev : account is any global, challenge, user or team pda still in an older layout
+pub fn migrate_account(payer: &Pubkey, account: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+    ];
+
+    build(accounts, ScreenWarsInstruction::MigrateAccount)
+}
+
+// dev : legacy users are migrated against their enrolled challenge, it sets next_sync_day
+pub fn migrate_user(payer: &Pubkey, user: &Pubkey, challenge_id: u32) -> Instruction {
+    let mut instruction = migrate_account(payer, &user_pda(user).0);
+    instruction.accounts.push(AccountMeta::new_readonly(
+        challenge_pda(challenge_id).0,
+        false,
+    ));
+
+    instruction
+}
+
+// dev : views only read, simulate them and decode the return data with client::views
+pub fn view_participant(user: &Pubkey, challenge_id: u32) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(challenge_pda(challenge_id).0, false),
+        AccountMeta::new_readonly(user_pda(user).0, false),
+    ];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::ViewParticipant(ViewInstructionData {
+            challenge_id,
+            days_missed: 0,
+        }),
+    )
+}
+
+// dev : days_missed 0 projects a single missed day, user must be enrolled in challenge_id
+pub fn view_penalty(user: &Pubkey, challenge_id: u32, days_missed: u8) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(challenge_pda(challenge_id).0, false),
+        AccountMeta::new_readonly(user_pda(user).0, false),
+    ];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::ViewPenalty(ViewInstructionData {
+            challenge_id,
+            days_missed,
+        }),
+    )
+}
+
+pub fn view_rewards(challenge_id: u32) -> Instruction {
+    let accounts = vec![AccountMeta::new_readonly(
+        challenge_pda(challenge_id).0,
+        false,
+    )];
+
+    build(
+        accounts,
+        ScreenWarsInstruction::ViewRewards(ViewInstructionData {
+            challenge_id,
+            days_missed: 0,
+        }),
+    )
+}
@@ -0,0 +1,17 @@
+use {crate::ScreenWarErrors, pinocchio::program_error::ProgramError};
+
+// dev : maps the u32 in ProgramError::Custom / InstructionError::Custom back to the program error
+pub fn decode_error(code: u32) -> Option<ScreenWarErrors> {
+    ScreenWarErrors::from_code(code)
+}
+
+pub fn decode_program_error(err: &ProgramError) -> Option<ScreenWarErrors> {
+    match err {
+        ProgramError::Custom(code) => decode_error(*code),
+        _ => None,
+    }
+}
+
+pub fn error_message(code: u32) -> Option<&'static str> {
+    decode_error(code).map(ScreenWarErrors::message)
+}
@@ -0,0 +1,8 @@
+pub mod accounts;
+pub mod attestation;
+pub mod builders;
+pub mod errors;
+pub mod pda;
+pub mod views;
+
+pub use {accounts::*, attestation::*, builders::*, errors::*, pda::*, views::*};
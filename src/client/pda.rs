@@ -0,0 +1,35 @@
+use solana_pubkey::{pubkey, Pubkey};
+
+pub const SYSTEM_PROGRAM_ID: Pubkey = pubkey!("11111111111111111111111111111111");
+pub const RENT_SYSVAR_ID: Pubkey = pubkey!("SysvarRent111111111111111111111111111111111");
+pub const CLOCK_SYSVAR_ID: Pubkey = pubkey!("SysvarC1ock11111111111111111111111111111111");
+pub const INSTRUCTIONS_SYSVAR_ID: Pubkey = pubkey!("Sysvar1nstructions1111111111111111111111111");
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+pub fn program_id() -> Pubkey {
+    Pubkey::new_from_array(crate::ID)
+}
+
+pub fn global_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"global"], &program_id())
+}
+
+pub fn challenge_pda(challenge_id: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"challenge", &challenge_id.to_le_bytes()], &program_id())
+}
+
+pub fn user_pda(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"user", user.as_ref()], &program_id())
+}
+
+pub fn team_pda(challenge_id: u32, team_id: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"team", &challenge_id.to_le_bytes(), &[team_id]],
+        &program_id(),
+    )
+}
+
+// dev : challenge_id 0 is the excusal applying to every challenge
+pub fn excusal_pda(challenge_id: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"excusal", &challenge_id.to_le_bytes()], &program_id())
+}
@@ -0,0 +1,18 @@
+use {
+    crate::instructions::{ParticipantView, PenaltyView, RewardsView, ViewData},
+    pinocchio::program_error::ProgramError,
+};
+
+// dev : data is the return data of a simulated view instruction
+
+pub fn decode_participant_view(data: &[u8]) -> Result<ParticipantView, ProgramError> {
+    ParticipantView::read(data)
+}
+
+pub fn decode_penalty_view(data: &[u8]) -> Result<PenaltyView, ProgramError> {
+    PenaltyView::read(data)
+}
+
+pub fn decode_rewards_view(data: &[u8]) -> Result<RewardsView, ProgramError> {
+    RewardsView::read(data)
+}
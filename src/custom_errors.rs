@@ -1,62 +1,333 @@
-use pinocchio::program_error::ProgramError;
+use pinocchio::program_error::{ProgramError, ToStr};
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+// dev : codes are part of the public interface, never renumber, only append
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ScreenWarErrors {
-    InvalidGlobalPDA,
+    InvalidGlobalPDA = 0,
 
-    InvalidChallengePDA,
+    InvalidChallengePDA = 1,
 
-    InvalidUserPDA,
+    InvalidUserPDA = 2,
 
-    InvalidPdaDataLen,
+    InvalidPdaDataLen = 3,
 
-    ChallengeCreationPaused,
+    ChallengeCreationPaused = 4,
 
-    ChallengeStartsTooSoon,
+    ChallengeStartsTooSoon = 5,
 
-    ChallengeStartsTooFar,
+    ChallengeStartsTooFar = 6,
 
-    ChallengeExceedsTwoHours,
+    ChallengeExceedsTwoHours = 7,
 
-    JoinedLate,
+    JoinedLate = 8,
 
-    ContentionPhase,
+    ContentionPhase = 9,
 
-    NotEnrolled,
+    NotEnrolled = 10,
 
-    ChallengeNotEnded,
+    ChallengeNotEnded = 11,
 
-    ContentionExpired,
+    ContentionExpired = 12,
 
-    LowerStreak,
+    LowerStreak = 13,
 
-    ChallengeStateAlreadySet,
+    ChallengeStateAlreadySet = 14,
 
-    OverClaim,
+    OverClaim = 15,
 
-    NotWinner,
+    NotWinner = 16,
 
-    NotCreator,
+    NotCreator = 17,
 
-    AlreadySynced,
+    AlreadySynced = 18,
 
-    ChallengeNotStarted,
+    ChallengeNotStarted = 19,
 
-    ChallengeEnded,
+    ChallengeEnded = 20,
 
-    NotSigner,
+    NotSigner = 21,
 
-    NotAdmin,
+    NotAdmin = 22,
 
-    IntegerBoundsExceeded,
+    IntegerBoundsExceeded = 23,
 
-    IntegerUnderflow,
+    IntegerUnderflow = 24,
 
-    IntegerOverflow,
+    IntegerOverflow = 25,
+
+    NotAllowlisted = 26,
+
+    ChallengeNotPrivate = 27,
+
+    ChallengeAlreadyStarted = 28,
+
+    InvalidTokenAccount = 29,
+
+    TokenGateNotMet = 30,
+
+    InvalidDuelOpponent = 31,
+
+    NotDuelist = 32,
+
+    NotDuelChallenge = 33,
+
+    DuelChallenge = 34,
+
+    DuelAlreadySettled = 35,
+
+    DuelNotSettled = 36,
+
+    InvalidTeamCount = 37,
+
+    InvalidTeam = 38,
+
+    InvalidTeamPDA = 39,
+
+    NotTeamChallenge = 40,
+
+    TeamChallenge = 41,
+
+    RewardAlreadyClaimed = 42,
+
+    InvalidDelegate = 43,
+
+    DelegateExpired = 44,
+
+    InsufficientPrepaidBalance = 45,
+
+    NotOracle = 46,
+
+    InvalidAccountDiscriminator = 47,
+
+    UnsupportedAccountVersion = 48,
+
+    AccountAlreadyInitialized = 49,
+
+    AccountNeedsMigration = 50,
+
+    AccountAlreadyMigrated = 51,
+
+    InvalidExtension = 52,
+
+    UnknownExtension = 53,
+
+    AccountNotWritable = 54,
+
+    InvalidAccountAddress = 55,
+
+    DuplicateAccount = 56,
+
+    ChallengeClosed = 57,
+
+    InvalidLifecycleTransition = 58,
+
+    MissingAttestation = 59,
+
+    InvalidAttestation = 60,
+
+    MockOracleDisabled = 61,
+
+    MissingDeviceSignature = 62,
+
+    DeviceReportMismatch = 63,
+
+    AttestationMismatch = 64,
+
+    InvalidSyncDay = 65,
+
+    NotAdminOrOracle = 66,
+
+    InvalidExcusalPDA = 67,
+
+    InvalidExcusedRange = 68,
+
+    ExcusedRangesFull = 69,
+
+    TeamRewardUnclaimed = 70,
+}
+
+impl ScreenWarErrors {
+    pub const ALL: [Self; 71] = [
+        Self::InvalidGlobalPDA,
+        Self::InvalidChallengePDA,
+        Self::InvalidUserPDA,
+        Self::InvalidPdaDataLen,
+        Self::ChallengeCreationPaused,
+        Self::ChallengeStartsTooSoon,
+        Self::ChallengeStartsTooFar,
+        Self::ChallengeExceedsTwoHours,
+        Self::JoinedLate,
+        Self::ContentionPhase,
+        Self::NotEnrolled,
+        Self::ChallengeNotEnded,
+        Self::ContentionExpired,
+        Self::LowerStreak,
+        Self::ChallengeStateAlreadySet,
+        Self::OverClaim,
+        Self::NotWinner,
+        Self::NotCreator,
+        Self::AlreadySynced,
+        Self::ChallengeNotStarted,
+        Self::ChallengeEnded,
+        Self::NotSigner,
+        Self::NotAdmin,
+        Self::IntegerBoundsExceeded,
+        Self::IntegerUnderflow,
+        Self::IntegerOverflow,
+        Self::NotAllowlisted,
+        Self::ChallengeNotPrivate,
+        Self::ChallengeAlreadyStarted,
+        Self::InvalidTokenAccount,
+        Self::TokenGateNotMet,
+        Self::InvalidDuelOpponent,
+        Self::NotDuelist,
+        Self::NotDuelChallenge,
+        Self::DuelChallenge,
+        Self::DuelAlreadySettled,
+        Self::DuelNotSettled,
+        Self::InvalidTeamCount,
+        Self::InvalidTeam,
+        Self::InvalidTeamPDA,
+        Self::NotTeamChallenge,
+        Self::TeamChallenge,
+        Self::RewardAlreadyClaimed,
+        Self::InvalidDelegate,
+        Self::DelegateExpired,
+        Self::InsufficientPrepaidBalance,
+        Self::NotOracle,
+        Self::InvalidAccountDiscriminator,
+        Self::UnsupportedAccountVersion,
+        Self::AccountAlreadyInitialized,
+        Self::AccountNeedsMigration,
+        Self::AccountAlreadyMigrated,
+        Self::InvalidExtension,
+        Self::UnknownExtension,
+        Self::AccountNotWritable,
+        Self::InvalidAccountAddress,
+        Self::DuplicateAccount,
+        Self::ChallengeClosed,
+        Self::InvalidLifecycleTransition,
+        Self::MissingAttestation,
+        Self::InvalidAttestation,
+        Self::MockOracleDisabled,
+        Self::MissingDeviceSignature,
+        Self::DeviceReportMismatch,
+        Self::AttestationMismatch,
+        Self::InvalidSyncDay,
+        Self::NotAdminOrOracle,
+        Self::InvalidExcusalPDA,
+        Self::InvalidExcusedRange,
+        Self::ExcusedRangesFull,
+        Self::TeamRewardUnclaimed,
+    ];
+
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::ALL.get(code as usize).copied()
+    }
+
+    pub fn message(self) -> &'static str {
+        match self {
+            Self::InvalidGlobalPDA => "Global account is not the global PDA",
+            Self::InvalidChallengePDA => "Challenge account is not the PDA for this challenge id",
+            Self::InvalidUserPDA => "User account is not the PDA for this wallet",
+            Self::InvalidPdaDataLen => "Account data has an unexpected length",
+            Self::ChallengeCreationPaused => "Challenge creation is paused",
+            Self::ChallengeStartsTooSoon => "Challenge must start at least one day from now",
+            Self::ChallengeStartsTooFar => "Challenge must start within one week from now",
+            Self::ChallengeExceedsTwoHours => "Daily timer can not exceed two hours",
+            Self::JoinedLate => "Challenge has already started, joining is closed",
+            Self::ContentionPhase => "Challenge is still in its contention period",
+            Self::NotEnrolled => "Wallet is not enrolled in this challenge",
+            Self::ChallengeNotEnded => "Challenge has not ended yet",
+            Self::ContentionExpired => "Contention period is over",
+            Self::LowerStreak => "Streak is lower than the current winner's",
+            Self::ChallengeStateAlreadySet => "Challenge state is already set",
+            Self::OverClaim => "Amount exceeds what can be claimed",
+            Self::NotWinner => "Wallet is not the challenge winner",
+            Self::NotCreator => "Wallet is not the challenge creator",
+            Self::AlreadySynced => "Already synced today",
+            Self::ChallengeNotStarted => "Challenge has not started yet",
+            Self::ChallengeEnded => "Challenge has ended",
+            Self::NotSigner => "Required signature is missing",
+            Self::NotAdmin => "Wallet is not the protocol admin",
+            Self::IntegerBoundsExceeded => "Value is out of bounds",
+            Self::IntegerUnderflow => "Arithmetic underflow",
+            Self::IntegerOverflow => "Arithmetic overflow",
+            Self::NotAllowlisted => "Wallet is not on the challenge allowlist",
+            Self::ChallengeNotPrivate => "Challenge is not private",
+            Self::ChallengeAlreadyStarted => "Challenge has already started",
+            Self::InvalidTokenAccount => "Token account does not belong to the wallet or gate mint",
+            Self::TokenGateNotMet => "Wallet does not hold enough of the gate token",
+            Self::InvalidDuelOpponent => "Creator can not duel themselves",
+            Self::NotDuelist => "Wallet is not part of this duel",
+            Self::NotDuelChallenge => "Challenge is not a duel",
+            Self::DuelChallenge => "Not available for duel challenges",
+            Self::DuelAlreadySettled => "Duel is already settled",
+            Self::DuelNotSettled => "Duel must be settled first",
+            Self::InvalidTeamCount => "Team count is out of range",
+            Self::InvalidTeam => "Team does not exist in this challenge",
+            Self::InvalidTeamPDA => "Team account is not the PDA for this team",
+            Self::NotTeamChallenge => "Challenge is not a team challenge",
+            Self::TeamChallenge => "Not available for team challenges",
+            Self::RewardAlreadyClaimed => "Reward is already claimed",
+            Self::InvalidDelegate => "Signer is not the registered sync delegate",
+            Self::DelegateExpired => "Sync delegate has expired",
+            Self::InsufficientPrepaidBalance => "Prepaid balance does not cover the deposit",
+            Self::NotOracle => "Signer is not the sync oracle",
+            Self::InvalidAccountDiscriminator => "Account is of a different type",
+            Self::UnsupportedAccountVersion => "Account version is not supported",
+            Self::AccountAlreadyInitialized => "Account is already initialized",
+            Self::AccountNeedsMigration => "Account must be migrated first",
+            Self::AccountAlreadyMigrated => "Account is already in the current layout",
+            Self::InvalidExtension => "Challenge extension is malformed",
+            Self::UnknownExtension => "Challenge extension type is unknown",
+            Self::AccountNotWritable => "Account must be writable",
+            Self::InvalidAccountAddress => "Account address is not the expected one",
+            Self::DuplicateAccount => "Same account passed more than once",
+            Self::ChallengeClosed => "Challenge is closed",
+            Self::InvalidLifecycleTransition => "Challenge can not move back to an earlier phase",
+            Self::MissingAttestation => "Sync needs an oracle attestation right before it",
+            Self::InvalidAttestation => "Oracle attestation is malformed",
+            Self::MockOracleDisabled => {
+                "Debug sync results are only accepted by mock oracle builds"
+            }
+            Self::MissingDeviceSignature => "Report is not signed by the registered device",
+            Self::DeviceReportMismatch => "Attested report differs from the device signed one",
+            Self::AttestationMismatch => "Attestation is for another participant or challenge",
+            Self::InvalidSyncDay => "Attestation is not for the next unsynced day",
+            Self::NotAdminOrOracle => "Only the admin or the oracle can excuse days",
+            Self::InvalidExcusalPDA => "Excusal account is not the PDA for this challenge",
+            Self::InvalidExcusedRange => "Excused range must end after it starts",
+            Self::ExcusedRangesFull => "No room left for another excused range",
+            Self::TeamRewardUnclaimed => "Claim the team reward before withdrawing",
+        }
+    }
 }
 
 impl From<ScreenWarErrors> for ProgramError {
     fn from(err: ScreenWarErrors) -> Self {
-        ProgramError::Custom(err as u32)
+        ProgramError::Custom(err.code())
+    }
+}
+
+impl TryFrom<u32> for ScreenWarErrors {
+    type Error = ProgramError;
+
+    fn try_from(code: u32) -> Result<Self, Self::Error> {
+        Self::from_code(code).ok_or(ProgramError::InvalidArgument)
+    }
+}
+
+impl ToStr for ScreenWarErrors {
+    fn to_str<E>(&self) -> &'static str
+    where
+        E: 'static + ToStr + TryFrom<u32>,
+    {
+        self.message()
     }
 }
@@ -0,0 +1,428 @@
+use pinocchio::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+// dev : every event is logged as one sol_log_data field
+// [EVENT_VERSION, tag, payload..] with little-endian integers and raw 32 byte pubkeys
+pub const EVENT_VERSION: u8 = 1;
+pub const MAX_EVENT_LEN: usize = 128;
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RewardKind {
+    Winner = 0,
+    Creator = 1,
+    TeamMember = 2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    Initialized {
+        admin: Pubkey,
+    },
+    ChallengeCreated {
+        challenge_id: u32,
+        creator: Pubkey,
+        daily_timer: i64,
+        start: i64,
+        end: i64,
+    },
+    ChallengeJoined {
+        challenge_id: u32,
+        user: Pubkey,
+        team_id: u8,
+        prepaid_balance: u64,
+    },
+    Synced {
+        challenge_id: u32,
+        user: Pubkey,
+        passed: Option<bool>, // dev : None when today was excused, neither passed nor failed
+        days_not_synced: u8,
+        streak: u8,
+        slashed: u64,
+        locked_balance: u64,
+    },
+    WinnerPositionClaimed {
+        challenge_id: u32,
+        claimer: Pubkey,
+        team_id: u8, // dev : 0 for individual challenges
+        score: u64,  // dev : streak, or scaled team average for team challenges
+    },
+    RewardsClaimed {
+        challenge_id: u32,
+        claimer: Pubkey,
+        kind: RewardKind,
+        amount: u64,
+    },
+    Withdrawn {
+        challenge_id: u32,
+        user: Pubkey,
+        locked_balance: u64,
+        refunded_prepaid: u64,
+    },
+    ProfitTaken {
+        admin: Pubkey,
+        amount: u64,
+    },
+    ChallengeCreationToggled {
+        paused: bool,
+    },
+    AllowlistUpdated {
+        challenge_id: u32,
+        allowlist_root: [u8; 32],
+    },
+    DuelSettled {
+        challenge_id: u32,
+        creator_payout: u64,
+        opponent_payout: u64,
+    },
+    SyncDelegateSet {
+        user: Pubkey,
+        delegate: Pubkey,
+        delegate_expiry: i64,
+    },
+    OracleSet {
+        oracle: Pubkey,
+    },
+    AccountMigrated {
+        account: Pubkey,
+        discriminator: u8,
+        version: u8,
+    },
+    DaysExcused {
+        challenge_id: u32, // dev : 0 for ranges excused in every challenge
+        authority: Pubkey,
+        start: i64,
+        end: i64,
+    },
+}
+
+impl Event {
+    pub fn tag(&self) -> u8 {
+        match self {
+            Event::Initialized { .. } => 0,
+            Event::ChallengeCreated { .. } => 1,
+            Event::ChallengeJoined { .. } => 2,
+            Event::Synced { .. } => 3,
+            Event::WinnerPositionClaimed { .. } => 4,
+            Event::RewardsClaimed { .. } => 5,
+            Event::Withdrawn { .. } => 6,
+            Event::ProfitTaken { .. } => 7,
+            Event::ChallengeCreationToggled { .. } => 8,
+            Event::AllowlistUpdated { .. } => 9,
+            Event::DuelSettled { .. } => 10,
+            Event::SyncDelegateSet { .. } => 11,
+            Event::OracleSet { .. } => 12,
+            Event::AccountMigrated { .. } => 13,
+            Event::DaysExcused { .. } => 14,
+        }
+    }
+
+    pub fn emit(&self) {
+        let mut buffer = [0u8; MAX_EVENT_LEN];
+        let len = self.encode(&mut buffer);
+        sol_log_data(&[&buffer[..len]]);
+    }
+
+    // dev : returns the number of bytes written, buffer must hold MAX_EVENT_LEN bytes
+    pub fn encode(&self, buffer: &mut [u8; MAX_EVENT_LEN]) -> usize {
+        let mut writer = EventWriter { buffer, len: 0 };
+        writer.put(&[EVENT_VERSION, self.tag()]);
+
+        match *self {
+            Event::Initialized { admin } => writer.put(&admin),
+            Event::ChallengeCreated {
+                challenge_id,
+                creator,
+                daily_timer,
+                start,
+                end,
+            } => {
+                writer.put(&challenge_id.to_le_bytes());
+                writer.put(&creator);
+                writer.put(&daily_timer.to_le_bytes());
+                writer.put(&start.to_le_bytes());
+                writer.put(&end.to_le_bytes());
+            }
+            Event::ChallengeJoined {
+                challenge_id,
+                user,
+                team_id,
+                prepaid_balance,
+            } => {
+                writer.put(&challenge_id.to_le_bytes());
+                writer.put(&user);
+                writer.put(&[team_id]);
+                writer.put(&prepaid_balance.to_le_bytes());
+            }
+            Event::Synced {
+                challenge_id,
+                user,
+                passed,
+                days_not_synced,
+                streak,
+                slashed,
+                locked_balance,
+            } => {
+                writer.put(&challenge_id.to_le_bytes());
+                writer.put(&user);
+                // dev : 2 marks an excused day, 0 and 1 keep their meaning from before excusals
+                let passed = match passed {
+                    Some(passed) => passed as u8,
+                    None => 2,
+                };
+                writer.put(&[passed, days_not_synced, streak]);
+                writer.put(&slashed.to_le_bytes());
+                writer.put(&locked_balance.to_le_bytes());
+            }
+            Event::WinnerPositionClaimed {
+                challenge_id,
+                claimer,
+                team_id,
+                score,
+            } => {
+                writer.put(&challenge_id.to_le_bytes());
+                writer.put(&claimer);
+                writer.put(&[team_id]);
+                writer.put(&score.to_le_bytes());
+            }
+            Event::RewardsClaimed {
+                challenge_id,
+                claimer,
+                kind,
+                amount,
+            } => {
+                writer.put(&challenge_id.to_le_bytes());
+                writer.put(&claimer);
+                writer.put(&[kind as u8]);
+                writer.put(&amount.to_le_bytes());
+            }
+            Event::Withdrawn {
+                challenge_id,
+                user,
+                locked_balance,
+                refunded_prepaid,
+            } => {
+                writer.put(&challenge_id.to_le_bytes());
+                writer.put(&user);
+                writer.put(&locked_balance.to_le_bytes());
+                writer.put(&refunded_prepaid.to_le_bytes());
+            }
+            Event::ProfitTaken { admin, amount } => {
+                writer.put(&admin);
+                writer.put(&amount.to_le_bytes());
+            }
+            Event::ChallengeCreationToggled { paused } => writer.put(&[paused as u8]),
+            Event::AllowlistUpdated {
+                challenge_id,
+                allowlist_root,
+            } => {
+                writer.put(&challenge_id.to_le_bytes());
+                writer.put(&allowlist_root);
+            }
+            Event::DuelSettled {
+                challenge_id,
+                creator_payout,
+                opponent_payout,
+            } => {
+                writer.put(&challenge_id.to_le_bytes());
+                writer.put(&creator_payout.to_le_bytes());
+                writer.put(&opponent_payout.to_le_bytes());
+            }
+            Event::SyncDelegateSet {
+                user,
+                delegate,
+                delegate_expiry,
+            } => {
+                writer.put(&user);
+                writer.put(&delegate);
+                writer.put(&delegate_expiry.to_le_bytes());
+            }
+            Event::OracleSet { oracle } => writer.put(&oracle),
+            Event::AccountMigrated {
+                account,
+                discriminator,
+                version,
+            } => {
+                writer.put(&account);
+                writer.put(&[discriminator, version]);
+            }
+            Event::DaysExcused {
+                challenge_id,
+                authority,
+                start,
+                end,
+            } => {
+                writer.put(&challenge_id.to_le_bytes());
+                writer.put(&authority);
+                writer.put(&start.to_le_bytes());
+                writer.put(&end.to_le_bytes());
+            }
+        }
+
+        writer.len
+    }
+
+    // dev : for clients, decodes one sol_log_data field emitted by the program
+    pub fn decode(data: &[u8]) -> Result<Self, ProgramError> {
+        let mut reader = EventReader { data };
+
+        if reader.u8()? != EVENT_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let event = match reader.u8()? {
+            0 => Event::Initialized {
+                admin: reader.pubkey()?,
+            },
+            1 => Event::ChallengeCreated {
+                challenge_id: reader.u32()?,
+                creator: reader.pubkey()?,
+                daily_timer: reader.i64()?,
+                start: reader.i64()?,
+                end: reader.i64()?,
+            },
+            2 => Event::ChallengeJoined {
+                challenge_id: reader.u32()?,
+                user: reader.pubkey()?,
+                team_id: reader.u8()?,
+                prepaid_balance: reader.u64()?,
+            },
+            3 => Event::Synced {
+                challenge_id: reader.u32()?,
+                user: reader.pubkey()?,
+                passed: match reader.u8()? {
+                    0 => Some(false),
+                    1 => Some(true),
+                    2 => None,
+                    _ => return Err(ProgramError::InvalidAccountData),
+                },
+                days_not_synced: reader.u8()?,
+                streak: reader.u8()?,
+                slashed: reader.u64()?,
+                locked_balance: reader.u64()?,
+            },
+            4 => Event::WinnerPositionClaimed {
+                challenge_id: reader.u32()?,
+                claimer: reader.pubkey()?,
+                team_id: reader.u8()?,
+                score: reader.u64()?,
+            },
+            5 => Event::RewardsClaimed {
+                challenge_id: reader.u32()?,
+                claimer: reader.pubkey()?,
+                kind: match reader.u8()? {
+                    0 => RewardKind::Winner,
+                    1 => RewardKind::Creator,
+                    2 => RewardKind::TeamMember,
+                    _ => return Err(ProgramError::InvalidAccountData),
+                },
+                amount: reader.u64()?,
+            },
+            6 => Event::Withdrawn {
+                challenge_id: reader.u32()?,
+                user: reader.pubkey()?,
+                locked_balance: reader.u64()?,
+                refunded_prepaid: reader.u64()?,
+            },
+            7 => Event::ProfitTaken {
+                admin: reader.pubkey()?,
+                amount: reader.u64()?,
+            },
+            8 => Event::ChallengeCreationToggled {
+                paused: reader.bool()?,
+            },
+            9 => Event::AllowlistUpdated {
+                challenge_id: reader.u32()?,
+                allowlist_root: reader.pubkey()?,
+            },
+            10 => Event::DuelSettled {
+                challenge_id: reader.u32()?,
+                creator_payout: reader.u64()?,
+                opponent_payout: reader.u64()?,
+            },
+            11 => Event::SyncDelegateSet {
+                user: reader.pubkey()?,
+                delegate: reader.pubkey()?,
+                delegate_expiry: reader.i64()?,
+            },
+            12 => Event::OracleSet {
+                oracle: reader.pubkey()?,
+            },
+            13 => Event::AccountMigrated {
+                account: reader.pubkey()?,
+                discriminator: reader.u8()?,
+                version: reader.u8()?,
+            },
+            14 => Event::DaysExcused {
+                challenge_id: reader.u32()?,
+                authority: reader.pubkey()?,
+                start: reader.i64()?,
+                end: reader.i64()?,
+            },
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+
+        // dev : trailing bytes mean the payload does not belong to this version
+        if !reader.data.is_empty() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(event)
+    }
+}
+
+struct EventWriter<'a> {
+    buffer: &'a mut [u8; MAX_EVENT_LEN],
+    len: usize,
+}
+
+impl EventWriter<'_> {
+    fn put(&mut self, bytes: &[u8]) {
+        self.buffer[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+}
+
+struct EventReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> EventReader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], ProgramError> {
+        if self.data.len() < N {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (value, rest) = self.data.split_at(N);
+        self.data = rest;
+
+        Ok(value.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, ProgramError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool, ProgramError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    fn u32(&mut self) -> Result<u32, ProgramError> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, ProgramError> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+
+    fn i64(&mut self) -> Result<i64, ProgramError> {
+        Ok(i64::from_le_bytes(self.take()?))
+    }
+
+    fn pubkey(&mut self) -> Result<Pubkey, ProgramError> {
+        self.take()
+    }
+}
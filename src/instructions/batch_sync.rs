@@ -0,0 +1,267 @@
+use {
+    crate::{
+        events::Event,
+        instructions::SyncLock,
+        state::{Challenge, Excusal, Global, Lifecycle, User},
+        utils::{check_account, check_unique, AccountCheck, Decoder},
+        ScreenWarErrors,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{
+            clock::{Clock, CLOCK_ID},
+            Sysvar,
+        },
+        ProgramResult,
+    },
+};
+
+pub struct BatchSync<'a> {
+    pub accounts: BatchSyncAccounts<'a>,
+    pub instruction_data: BatchSyncInstructionData<'a>,
+}
+
+pub struct BatchSyncAccounts<'a> {
+    pub oracle: &'a AccountInfo,
+    pub global: &'a AccountInfo,
+    pub challenge: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+    pub user_pdas: &'a [AccountInfo], // dev : one user_pda per result, in the same order
+    pub excusals: &'a [AccountInfo],  // dev : up to Excusal::MAX_SCOPES, applied to every user
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BatchSyncInstructionData<'a> {
+    pub challenge_id: u32,
+    pub results: &'a [u8], // dev : RESULT_LEN bytes per user => [user_passed, days_not_synced]
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], BatchSyncInstructionData<'a>)> for BatchSync<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo], BatchSyncInstructionData<'a>),
+    ) -> Result<Self, Self::Error> {
+        let users = instruction_data.results.len() / BatchSync::RESULT_LEN;
+        let accounts = BatchSyncAccounts::try_from((accounts, users))?;
+
+        // validate correct challenge pda
+        Challenge::validate_pda(accounts.challenge, instruction_data.challenge_id)?;
+
+        // return Self
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+// dev : users is the number of results, the user_pdas come first and the excusals after them
+impl<'a> TryFrom<(&'a [AccountInfo], usize)> for BatchSyncAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from((accounts, users): (&'a [AccountInfo], usize)) -> Result<Self, Self::Error> {
+        let [oracle, global, challenge, clock_sysvar, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // validate results and user_pdas pair up exactly, nothing is dropped
+        if remaining.len() < users || remaining.len() - users > Excusal::MAX_SCOPES {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (user_pdas, excusals) = remaining.split_at(users);
+
+        // dev : later oracle key is validated against global.oracle in validate_oracle() function
+        check_account(oracle, &[AccountCheck::Signer])?;
+        check_account(global, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(challenge, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(clock_sysvar, &[AccountCheck::Address(&CLOCK_ID)])?;
+        for user_pda in user_pdas {
+            check_account(user_pda, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        }
+
+        // dev : a participant listed twice would be synced twice in one call
+        check_unique(user_pdas)?;
+
+        Global::validate_pda(global)?;
+
+        Ok(Self {
+            oracle,
+            global,
+            challenge,
+            clock_sysvar,
+            user_pdas,
+            excusals,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for BatchSyncInstructionData<'a> {
+    type Error = ProgramError;
+
+    fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
+        // dev
+        // first 4 bytes would be challenge_id
+        // remaining bytes would be the results, RESULT_LEN bytes per user
+        let mut data = Decoder::new(instruction_data);
+        let challenge_id = data.u32()?;
+        let results = data.rest();
+
+        let mut chunks = results.chunks_exact(BatchSync::RESULT_LEN);
+        if results.is_empty() || !chunks.remainder().is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        // dev : user_passed is a bool on the wire, same rule as Decoder::bool()
+        if chunks.any(|result| result[0] > 1) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self {
+            challenge_id,
+            results,
+        })
+    }
+}
+
+impl<'a> BatchSync<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &15;
+    pub const RESULT_LEN: usize = 2;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // validate oracle
+        {
+            let global = Global::from_account_info(self.accounts.global)?;
+            Self::validate_oracle(&global, self.accounts.oracle)?;
+        }
+
+        let retained_per_day = SyncLock::read_retained_per_day(self.accounts.challenge)?;
+
+        // get mutable ref to challenge pda
+        let mut challenge = Challenge::from_account_info_mut(self.accounts.challenge)?;
+
+        // validations
+        let now = Clock::get()?.unix_timestamp;
+        challenge.lifecycle(now)?.validate(&[Lifecycle::Active])?;
+        Self::validate_challenge_is_not_team(challenge.team_count)?;
+        let today = challenge.day(now)?;
+
+        let results = self.instruction_data.results.chunks_exact(Self::RESULT_LEN);
+
+        for (user_pda_account, result) in self.accounts.user_pdas.iter().zip(results) {
+            let user_passed_today = result[0] != 0;
+            let days_not_synced = result[1];
+
+            self.sync_user(
+                &mut challenge,
+                user_pda_account,
+                user_passed_today,
+                days_not_synced,
+                today,
+                retained_per_day,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn sync_user(
+        &self,
+        challenge: &mut Challenge,
+        user_pda_account: &AccountInfo,
+        user_passed_today: bool,
+        days_not_synced: u8,
+        today: u32,
+        retained_per_day: u32,
+    ) -> ProgramResult {
+        if !user_pda_account.is_owned_by(&crate::ID) {
+            return Err(ScreenWarErrors::InvalidUserPDA.into());
+        }
+
+        let mut user_pda = User::from_account_info_mut(user_pda_account)?;
+
+        if user_pda.challenge_id != challenge.challenge_id {
+            return Err(ScreenWarErrors::NotEnrolled.into());
+        }
+
+        Self::validate_sync_day(&user_pda, days_not_synced, today)?;
+
+        // excused days are neither deposited for nor judged, same as SyncLock
+        let (days_excused, today_excused) = SyncLock::excused_days(
+            self.accounts.excusals.iter(),
+            challenge,
+            user_pda.next_sync_day,
+            days_not_synced,
+        )?;
+        let days_not_synced = days_not_synced - days_excused;
+        let settled_today = (!today_excused).then_some(user_passed_today);
+
+        // deposit, oracle has no wallet to charge so prepaid balance must cover it
+        let lamports = (days_not_synced as u64 + u64::from(!today_excused))
+            .checked_mul(SyncLock::DAILY_LAMPORTS)
+            .ok_or(ScreenWarErrors::IntegerOverflow)?;
+
+        if lamports > user_pda.prepaid_balance {
+            return Err(ScreenWarErrors::InsufficientPrepaidBalance.into());
+        }
+
+        user_pda.prepaid_balance -= lamports;
+        SyncLock::draw_prepaid_lamports(user_pda_account, self.accounts.global, lamports)?;
+
+        let slashed = SyncLock::apply_sync_result(
+            challenge,
+            &mut user_pda,
+            settled_today,
+            days_not_synced,
+            days_excused,
+            retained_per_day,
+        )?;
+
+        Event::Synced {
+            challenge_id: challenge.challenge_id,
+            user: user_pda.user,
+            passed: settled_today,
+            days_not_synced,
+            streak: user_pda.streak,
+            slashed,
+            locked_balance: user_pda.locked_balance,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    pub fn validate_oracle(global: &Global, caller: &AccountInfo) -> ProgramResult {
+        if global.oracle.eq(&Pubkey::default()) || global.oracle.ne(caller.key()) {
+            return Err(ScreenWarErrors::NotOracle.into());
+        }
+
+        Ok(())
+    }
+
+    // dev : a batch settles today, so the missed days must end right before it
+    // a participant already synced today has next_sync_day past it and is refused
+    pub fn validate_sync_day(user_pda: &User, days_not_synced: u8, today: u32) -> ProgramResult {
+        let synced_day = user_pda
+            .next_sync_day
+            .checked_add(days_not_synced as u32)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        if synced_day.ne(&today) {
+            return Err(ScreenWarErrors::InvalidSyncDay.into());
+        }
+
+        Ok(())
+    }
+
+    // dev : team aggregates need a team_pda per member, sync team challenges through SyncLock
+    pub fn validate_challenge_is_not_team(team_count: u8) -> ProgramResult {
+        if team_count > 0 {
+            return Err(ScreenWarErrors::TeamChallenge.into());
+        }
+
+        Ok(())
+    }
+}
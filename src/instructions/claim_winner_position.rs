@@ -1,13 +1,18 @@
 use {
     crate::{
-        state::{Challenge, User},
+        events::Event,
+        state::{Challenge, Lifecycle, Team, User},
+        utils::{check_account, AccountCheck, Decoder},
         ScreenWarErrors,
     },
     pinocchio::{
         account_info::AccountInfo,
         program_error::ProgramError,
-        pubkey::{find_program_address, Pubkey},
-        sysvars::{clock::Clock, Sysvar},
+        pubkey::Pubkey,
+        sysvars::{
+            clock::{Clock, CLOCK_ID},
+            Sysvar,
+        },
         ProgramResult,
     },
 };
@@ -22,30 +27,26 @@ pub struct ClaimWinnerPositionAccounts<'a> {
     pub challenge: &'a AccountInfo,
     pub user_pda: &'a AccountInfo,
     pub clock_sysvar: &'a AccountInfo,
+    pub team_pda: Option<&'a AccountInfo>, // dev : required only for team challenges
 }
 
+#[derive(Debug, PartialEq)]
 pub struct ClaimWinnerPositionInstructionData {
     pub challenge_id: u32,
 }
 
-impl<'a> TryFrom<(&'a [AccountInfo], &'a [u8])> for ClaimWinnerPosition<'a> {
+impl<'a> TryFrom<(&'a [AccountInfo], ClaimWinnerPositionInstructionData)>
+    for ClaimWinnerPosition<'a>
+{
     type Error = ProgramError;
 
     fn try_from(
-        (accounts, instruction_data): (&'a [AccountInfo], &'a [u8]),
+        (accounts, instruction_data): (&'a [AccountInfo], ClaimWinnerPositionInstructionData),
     ) -> Result<Self, Self::Error> {
         let accounts = ClaimWinnerPositionAccounts::try_from(accounts)?;
-        let instruction_data = ClaimWinnerPositionInstructionData::try_from(instruction_data)?;
 
         // validate correct challenge pda
-        let (challenge_pda_key, _) = find_program_address(
-            &[b"challenge", &instruction_data.challenge_id.to_le_bytes()],
-            &crate::ID,
-        );
-
-        if challenge_pda_key.ne(accounts.challenge.key()) {
-            return Err(ScreenWarErrors::InvalidChallengePDA.into());
-        }
+        Challenge::validate_pda(accounts.challenge, instruction_data.challenge_id)?;
 
         // return Self
         Ok(Self {
@@ -59,25 +60,23 @@ impl<'a> TryFrom<&'a [AccountInfo]> for ClaimWinnerPositionAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [user, challenge, user_pda, clock_sysvar] = accounts else {
+        let [user, challenge, user_pda, clock_sysvar, remaining @ ..] = accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        if !user.is_signer() {
-            return Err(ScreenWarErrors::NotSigner.into());
-        }
-
-        let (user_pda_key, _) = find_program_address(&[b"user", user.key().as_slice()], &crate::ID);
+        check_account(user, &[AccountCheck::Signer])?;
+        check_account(challenge, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(user_pda, &[AccountCheck::Owned])?;
+        check_account(clock_sysvar, &[AccountCheck::Address(&CLOCK_ID)])?;
 
-        if (&user_pda_key).ne(user_pda.key()) {
-            return Err(ScreenWarErrors::InvalidUserPDA.into());
-        };
+        User::validate_pda(user_pda, user.key())?;
 
         Ok(Self {
             user,
             challenge,
             user_pda,
             clock_sysvar,
+            team_pda: remaining.first(),
         })
     }
 }
@@ -86,11 +85,9 @@ impl<'a> TryFrom<&'a [u8]> for ClaimWinnerPositionInstructionData {
     type Error = ProgramError;
 
     fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
-        if instruction_data.len().ne(&4usize) {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        let challenge_id = u32::from_le_bytes(instruction_data.try_into().unwrap());
+        let mut data = Decoder::new(instruction_data);
+        let challenge_id = data.u32()?;
+        data.finish()?;
 
         Ok(Self { challenge_id })
     }
@@ -101,48 +98,54 @@ impl<'a> ClaimWinnerPosition<'a> {
 
     pub fn process(&mut self) -> ProgramResult {
         // get the mutable reference to challenge and simple reference to User pdas
-        let mut challenge_raw_data = self.accounts.challenge.try_borrow_mut_data()?;
-        let challenge = Challenge::load_mut(&mut challenge_raw_data)?;
+        let mut challenge = Challenge::from_account_info_mut(self.accounts.challenge)?;
 
-        let user_pda_raw_data = self.accounts.user_pda.try_borrow_data()?;
-        let user_pda = User::load(&user_pda_raw_data)?;
+        let user_pda = User::from_account_info(self.accounts.user_pda)?;
 
         // validations
         let now = Clock::get()?.unix_timestamp;
-        Self::validate_challenge_has_ended(now, challenge.end)?;
-        Self::validate_reward_claiming_has_not_started(now, challenge.end)?;
+        Self::validate_challenge_is_not_duel(challenge.is_duel)?;
+        challenge
+            .lifecycle(now)?
+            .validate(&[Lifecycle::Contention])?;
         Self::validate_user_is_enrolled_in_challenge(
             challenge.challenge_id,
             user_pda.challenge_id,
         )?;
 
+        // team challenges race on team score, any member can claim for their team
+        if challenge.team_count > 0 {
+            return Self::claim_team_position(&mut challenge, &user_pda, self.accounts.team_pda);
+        }
+
         // set winner
         if challenge.winner == Pubkey::default() {
-            Self::write_winner(challenge, user_pda)?;
+            Self::write_winner(&mut challenge, &user_pda)?;
         } else {
             if challenge.winner_streak > user_pda.streak {
                 return Err(ScreenWarErrors::LowerStreak.into());
             }
 
-            Self::write_winner(challenge, user_pda)?;
+            Self::write_winner(&mut challenge, &user_pda)?;
         }
-        Ok(())
-    }
 
-    pub fn validate_challenge_has_ended(now: i64, end: i64) -> ProgramResult {
-        if end > now {
-            return Err(ScreenWarErrors::ChallengeNotEnded.into());
-        };
+        Event::WinnerPositionClaimed {
+            challenge_id: challenge.challenge_id,
+            claimer: user_pda.user,
+            team_id: 0,
+            score: user_pda.streak as u64,
+        }
+        .emit();
 
         Ok(())
     }
 
-    pub fn validate_reward_claiming_has_not_started(now: i64, end: i64) -> ProgramResult {
-        let five_days = 5 * 24 * 60 * 60;
-
-        if now > end + five_days {
-            return Err(ScreenWarErrors::ContentionExpired.into());
+    // dev : duels are decided head-to-head in SettleDuel, there is no contention race
+    pub fn validate_challenge_is_not_duel(is_duel: bool) -> ProgramResult {
+        if is_duel {
+            return Err(ScreenWarErrors::DuelChallenge.into());
         }
+
         Ok(())
     }
 
@@ -163,4 +166,46 @@ impl<'a> ClaimWinnerPosition<'a> {
 
         Ok(())
     }
+
+    pub fn claim_team_position(
+        challenge: &mut Challenge,
+        user_pda: &User,
+        team_pda: Option<&AccountInfo>,
+    ) -> ProgramResult {
+        let team_pda = team_pda.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        Team::validate_pda(team_pda, challenge.challenge_id, user_pda.team_id)?;
+
+        let team = Team::from_account_info(team_pda)?;
+
+        let team_score = Self::calculate_team_score(team.streak_sum, team.members)?;
+
+        if challenge.winning_team != 0 && challenge.winning_team_score > team_score {
+            return Err(ScreenWarErrors::LowerStreak.into());
+        }
+
+        challenge.winning_team = team.team_id;
+        challenge.winning_team_score = team_score;
+
+        Event::WinnerPositionClaimed {
+            challenge_id: challenge.challenge_id,
+            claimer: user_pda.user,
+            team_id: team.team_id,
+            score: team_score,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    // dev : team score is the average member streak, scaled so uneven team sizes compare fairly
+    pub fn calculate_team_score(streak_sum: u64, members: u32) -> Result<u64, ProgramError> {
+        const SCALE: u64 = 1_000_000;
+
+        streak_sum
+            .checked_mul(SCALE)
+            .ok_or(ScreenWarErrors::IntegerOverflow)?
+            .checked_div(members as u64)
+            .ok_or(ScreenWarErrors::IntegerUnderflow.into())
+    }
 }
@@ -1,14 +1,20 @@
 use {
     crate::{
         custom_errors::ScreenWarErrors,
-        state::{Challenge, Global},
+        events::Event,
+        state::{validate_extensions, Challenge, Global},
+        utils::{check_account, check_system_program, AccountCheck, Decoder},
     },
     pinocchio::{
         account_info::AccountInfo,
         instruction::{Seed, Signer},
         program_error::ProgramError,
-        pubkey::find_program_address,
-        sysvars::{clock::Clock, rent::Rent, Sysvar},
+        pubkey::{find_program_address, Pubkey},
+        sysvars::{
+            clock::Clock,
+            rent::{Rent, RENT_ID},
+            Sysvar,
+        },
         ProgramResult,
     },
     pinocchio_system::instructions::CreateAccount,
@@ -16,7 +22,7 @@ use {
 
 pub struct CreateChallenge<'a> {
     pub accounts: CreateChallengeAccounts<'a>,
-    pub instruction_data: CreateChallengeInstructionData,
+    pub instruction_data: CreateChallengeInstructionData<'a>,
 }
 
 pub struct CreateChallengeAccounts<'a> {
@@ -29,17 +35,38 @@ pub struct CreateChallengeAccounts<'a> {
     pub current_challenge_id: u32,
 }
 
-pub struct CreateChallengeInstructionData {
+#[derive(Debug, PartialEq)]
+pub struct CreateChallengeInstructionData<'a> {
     pub start_time: i64,
     pub daily_timer: i64,
+    pub allowlist_root: Option<[u8; 32]>, // dev : Some(root) marks the challenge private (invite-only)
+    pub token_gate: Option<TokenGate>,
+    pub duel: Option<Duel>,
+    pub team_count: Option<u8>,
+    pub extensions: &'a [u8], // dev : tlv entries copied verbatim after the Challenge bytes
 }
 
-impl<'a> TryFrom<(&'a [AccountInfo], &'a [u8])> for CreateChallenge<'a> {
+#[derive(Debug, PartialEq)]
+pub struct TokenGate {
+    pub mint: Pubkey,
+    pub min_balance: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Duel {
+    pub opponent: Pubkey,
+    pub stake: u64,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], CreateChallengeInstructionData<'a>)> for CreateChallenge<'a> {
     type Error = ProgramError;
 
-    fn try_from((accounts, data): (&'a [AccountInfo], &'a [u8])) -> Result<Self, Self::Error> {
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo], CreateChallengeInstructionData<'a>),
+    ) -> Result<Self, Self::Error> {
         let accounts = CreateChallengeAccounts::try_from(accounts)?;
-        let instruction_data = CreateChallengeInstructionData::try_from(data)?;
+
+        CreateChallenge::validate_start_time(instruction_data.start_time)?;
 
         Ok(Self {
             accounts,
@@ -56,15 +83,17 @@ impl<'a> TryFrom<&'a [AccountInfo]> for CreateChallengeAccounts<'a> {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
+        check_account(creator, &[AccountCheck::Signer, AccountCheck::Writable])?;
+        check_account(global_pda, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(challenge_pda, &[AccountCheck::Writable])?;
+        check_account(rent_sysvar, &[AccountCheck::Address(&RENT_ID)])?;
+        check_system_program(system_program)?;
+
         // validate global pda
-        let (global_pda_key, _) = find_program_address(&[b"global"], &crate::ID);
-        if global_pda.key().ne(&global_pda_key) {
-            return Err(ProgramError::InvalidSeeds);
-        }
+        Global::validate_pda(global_pda)?;
 
         // fetching global_pda data
-        let global_pda_raw_data = global_pda.try_borrow_data()?;
-        let global = Global::load(&global_pda_raw_data)?;
+        let global = Global::from_account_info(global_pda)?;
         let current_challenge_id = global.challenge_ids;
 
         // validate challenge pda
@@ -95,51 +124,118 @@ impl<'a> TryFrom<&'a [AccountInfo]> for CreateChallengeAccounts<'a> {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for CreateChallengeInstructionData {
+impl<'a> TryFrom<&'a [u8]> for CreateChallengeInstructionData<'a> {
     type Error = ProgramError;
 
     fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
-        if instruction_data.len().ne(&(16 as usize)) {
-            return Err(ProgramError::InvalidInstructionData);
-        };
-
         // dev
         // first 8 bytes would be start_time
         // second 8 bytes would be daily_timer
-        let (start, timer) = instruction_data.split_at(8);
+        // optional trailing Option<[u8; 32]> would be allowlist_root : [tag, root..]
+        // optional trailing Option<TokenGate> would be token_gate : [tag, mint.., min_balance..]
+        // optional trailing Option<Duel> would be duel : [tag, opponent.., stake..]
+        // optional trailing Option<u8> would be team_count : [tag, count]
+        // any bytes left are extensions : [type, len, value..]*
+        // dev : omitted trailing options are read as None, so old 16 byte payloads stay valid
+        let mut data = Decoder::new(instruction_data);
+        let start_time = data.i64()?;
+        let daily_timer = data.i64()?;
+
+        let allowlist_root: Option<[u8; 32]> = match data.option(32)? {
+            Some(root) => Some(Decoder::new(root).array()?),
+            None => None,
+        };
 
-        let start_time = i64::from_le_bytes(start.try_into().unwrap());
-        let daily_timer = i64::from_le_bytes(timer.try_into().unwrap());
+        let token_gate: Option<TokenGate> = match data.option(40)? {
+            Some(gate) => {
+                let mut gate = Decoder::new(gate);
+                Some(TokenGate {
+                    mint: gate.pubkey()?,
+                    min_balance: gate.u64()?,
+                })
+            }
+            None => None,
+        };
+
+        let duel: Option<Duel> = match data.option(40)? {
+            Some(duel) => {
+                let mut duel = Decoder::new(duel);
+                Some(Duel {
+                    opponent: duel.pubkey()?,
+                    stake: duel.u64()?,
+                })
+            }
+            None => None,
+        };
+
+        let team_count: Option<u8> = match data.option(1)? {
+            Some(count) => Some(Decoder::new(count).u8()?),
+            None => None,
+        };
+
+        let extensions = data.rest();
+        validate_extensions(extensions)?;
 
         // validations
-        let now = Clock::get()?.unix_timestamp;
+        // dev : start_time depends on the clock, it is checked in validate_start_time() instead
         let two_hours = 2 * (60 * 60);
-        let one_day = two_hours * 12;
-        let one_week = one_day * 7;
 
-        if start_time < now + one_day {
-            return Err(ScreenWarErrors::ChallengeStartsTooSoon)?;
+        if daily_timer >= two_hours {
+            return Err(ScreenWarErrors::ChallengeExceedsTwoHours)?;
         }
 
-        if start_time >= now + one_week {
-            return Err(ScreenWarErrors::ChallengeStartsTooFar)?;
+        if let Some(duel) = &duel {
+            if duel.opponent.eq(&Pubkey::default()) || duel.stake == 0 {
+                return Err(ScreenWarErrors::InvalidDuelOpponent.into());
+            }
         }
 
-        if daily_timer >= two_hours {
-            return Err(ScreenWarErrors::ChallengeExceedsTwoHours)?;
+        if let Some(team_count) = team_count {
+            if !(2..=CreateChallenge::MAX_TEAMS).contains(&team_count) || duel.is_some() {
+                return Err(ScreenWarErrors::InvalidTeamCount.into());
+            }
         }
 
         Ok(Self {
             start_time,
             daily_timer,
+            allowlist_root,
+            token_gate,
+            duel,
+            team_count,
+            extensions,
         })
     }
 }
 
 impl<'a> CreateChallenge<'a> {
     pub const DISCRIMINATOR: &'a u8 = &1;
+    pub const MAX_TEAMS: u8 = 16;
+
+    pub fn validate_start_time(start_time: i64) -> ProgramResult {
+        let now = Clock::get()?.unix_timestamp;
+        let one_day = 24 * 60 * 60;
+        let one_week = one_day * 7;
+
+        if start_time < now + one_day {
+            return Err(ScreenWarErrors::ChallengeStartsTooSoon)?;
+        }
+
+        if start_time >= now + one_week {
+            return Err(ScreenWarErrors::ChallengeStartsTooFar)?;
+        }
+
+        Ok(())
+    }
 
     pub fn process(&mut self) -> ProgramResult {
+        //// validate creator is not dueling themselves
+        if let Some(duel) = &self.instruction_data.duel {
+            if duel.opponent.eq(self.accounts.creator.key()) {
+                return Err(ScreenWarErrors::InvalidDuelOpponent.into());
+            }
+        }
+
         //// initialize challenge pda data
         let three_weeks = 3 * 7 * 24 * 60 * 60;
         let end_time = self
@@ -148,7 +244,7 @@ impl<'a> CreateChallenge<'a> {
             .checked_add(three_weeks)
             .ok_or(ProgramError::ArithmeticOverflow)?;
 
-        let space = Challenge::LEN;
+        let space = Challenge::LEN + self.instruction_data.extensions.len();
         let rent = Rent::get()?.minimum_balance(space);
         let id_binding = self.accounts.current_challenge_id.to_le_bytes();
         let bump_binding = [self.accounts.challenge_bump];
@@ -170,7 +266,7 @@ impl<'a> CreateChallenge<'a> {
         .invoke_signed(&[signers])?;
 
         let mut challenge_pda_raw_data = self.accounts.challenge_pda.try_borrow_mut_data()?;
-        let challenge: &mut Challenge = Challenge::load_mut(&mut challenge_pda_raw_data)?;
+        let challenge = Challenge::init(&mut challenge_pda_raw_data)?;
 
         *challenge = Challenge {
             creator: *self.accounts.creator.key(),
@@ -178,18 +274,53 @@ impl<'a> CreateChallenge<'a> {
             daily_timer: self.instruction_data.daily_timer,
             start: self.instruction_data.start_time,
             end: end_time,
+            is_private: self.instruction_data.allowlist_root.is_some(),
+            allowlist_root: self.instruction_data.allowlist_root.unwrap_or_default(),
+            gate_mint: self
+                .instruction_data
+                .token_gate
+                .as_ref()
+                .map_or(Pubkey::default(), |gate| gate.mint),
+            gate_min_balance: self
+                .instruction_data
+                .token_gate
+                .as_ref()
+                .map_or(0, |gate| gate.min_balance),
+            is_duel: self.instruction_data.duel.is_some(),
+            opponent: self
+                .instruction_data
+                .duel
+                .as_ref()
+                .map_or(Pubkey::default(), |duel| duel.opponent),
+            duel_stake: self
+                .instruction_data
+                .duel
+                .as_ref()
+                .map_or(0, |duel| duel.stake),
+            team_count: self.instruction_data.team_count.unwrap_or(0),
             bump: self.accounts.challenge_bump,
-            ..Challenge::default()
+            ..Challenge::new()
         };
 
+        challenge_pda_raw_data[Challenge::LEN..].copy_from_slice(self.instruction_data.extensions);
+        drop(challenge_pda_raw_data);
+
         //// increment global challenge ids in global_pda
-        let mut global_pda_raw_data = self.accounts.global_pda.try_borrow_mut_data()?;
-        let global = Global::load_mut(&mut global_pda_raw_data)?;
+        let mut global = Global::from_account_info_mut(self.accounts.global_pda)?;
         global.challenge_ids = global
             .challenge_ids
             .checked_add(1)
             .ok_or(ProgramError::ArithmeticOverflow)?;
 
+        Event::ChallengeCreated {
+            challenge_id: self.accounts.current_challenge_id,
+            creator: *self.accounts.creator.key(),
+            daily_timer: self.instruction_data.daily_timer,
+            start: self.instruction_data.start_time,
+            end: end_time,
+        }
+        .emit();
+
         Ok(())
     }
 }
@@ -0,0 +1,179 @@
+use {
+    crate::{
+        events::Event,
+        state::{Excusal, ExcusedRange, Global},
+        utils::{check_account, check_system_program, AccountCheck, Decoder},
+        ScreenWarErrors,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{Seed, Signer},
+        program_error::ProgramError,
+        pubkey::{find_program_address, Pubkey},
+        sysvars::{rent::Rent, Sysvar},
+        ProgramResult,
+    },
+    pinocchio_system::instructions::CreateAccount,
+};
+
+pub struct ExcuseDays<'a> {
+    pub accounts: ExcuseDaysAccounts<'a>,
+    pub instruction_data: ExcuseDaysInstructionData,
+}
+
+pub struct ExcuseDaysAccounts<'a> {
+    pub authority: &'a AccountInfo,
+    pub global: &'a AccountInfo,
+    pub excusal: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExcuseDaysInstructionData {
+    pub challenge_id: u32, // dev : Excusal::GLOBAL_SCOPE excuses the range in every challenge
+    pub start: i64,
+    pub end: i64,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], ExcuseDaysInstructionData)> for ExcuseDays<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo], ExcuseDaysInstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = ExcuseDaysAccounts::try_from(accounts)?;
+
+        // return Self
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ExcuseDaysAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, global, excusal, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // dev : later authority key is validated against global.admin and global.oracle in validate_authority() function
+        check_account(authority, &[AccountCheck::Signer, AccountCheck::Writable])?;
+        check_account(global, &[AccountCheck::Owned])?;
+        check_account(excusal, &[AccountCheck::Writable])?;
+        check_system_program(system_program)?;
+
+        Global::validate_pda(global)?;
+
+        Ok(Self {
+            authority,
+            global,
+            excusal,
+            system_program,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ExcuseDaysInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
+        // dev
+        // first 4 bytes would be challenge_id
+        // next 8 bytes would be start, then 8 bytes end, both unix timestamps
+        let mut data = Decoder::new(instruction_data);
+        let challenge_id = data.u32()?;
+        let start = data.i64()?;
+        let end = data.i64()?;
+        data.finish()?;
+
+        Ok(Self {
+            challenge_id,
+            start,
+            end,
+        })
+    }
+}
+
+impl<'a> ExcuseDays<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &20;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let global = Global::from_account_info(self.accounts.global)?;
+        Self::validate_authority(&global, self.accounts.authority)?;
+
+        let (challenge_id, start, end) = (
+            self.instruction_data.challenge_id,
+            self.instruction_data.start,
+            self.instruction_data.end,
+        );
+
+        if start >= end {
+            return Err(ScreenWarErrors::InvalidExcusedRange.into());
+        }
+
+        //// first range for this scope pays for the excusal pda
+        let id_binding = challenge_id.to_le_bytes();
+        let (excusal_key, excusal_bump) =
+            find_program_address(&[b"excusal", &id_binding], &crate::ID);
+
+        if excusal_key.ne(self.accounts.excusal.key()) {
+            return Err(ScreenWarErrors::InvalidExcusalPDA.into());
+        }
+
+        if !self.accounts.excusal.is_owned_by(&crate::ID) {
+            let space = Excusal::LEN;
+            let rent = Rent::get()?.minimum_balance(space);
+            let bump_binding = [excusal_bump];
+            let seeds = &[
+                Seed::from(b"excusal"),
+                Seed::from(&id_binding),
+                Seed::from(&bump_binding),
+            ];
+            let pda_signature = Signer::from(seeds);
+
+            CreateAccount {
+                from: self.accounts.authority,
+                to: self.accounts.excusal,
+                lamports: rent,
+                space: space as u64,
+                owner: &crate::ID,
+            }
+            .invoke_signed(&[pda_signature])?;
+
+            let mut excusal_ptr = self.accounts.excusal.try_borrow_mut_data()?;
+            *Excusal::init(&mut excusal_ptr)? = Excusal {
+                challenge_id,
+                bump: excusal_bump,
+                ..Excusal::new()
+            };
+        }
+
+        //// record the range
+        Excusal::from_account_info_mut(self.accounts.excusal)?.push(ExcusedRange { start, end })?;
+
+        Event::DaysExcused {
+            challenge_id,
+            authority: *self.accounts.authority.key(),
+            start,
+            end,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    // dev : the oracle is the one that knows it was down, the admin can step in when it can not
+    pub fn validate_authority(global: &Global, caller: &AccountInfo) -> ProgramResult {
+        let is_admin = global.admin.eq(caller.key());
+        let is_oracle = global.oracle.ne(&Pubkey::default()) && global.oracle.eq(caller.key());
+
+        if !is_admin && !is_oracle {
+            return Err(ScreenWarErrors::NotAdminOrOracle.into());
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,19 @@
 use {
-    crate::{custom_errors::ScreenWarErrors, state::Global},
+    crate::{
+        custom_errors::ScreenWarErrors,
+        events::Event,
+        state::Global,
+        utils::{check_account, check_system_program, AccountCheck},
+    },
     pinocchio::{
         account_info::AccountInfo,
         instruction::{Seed, Signer},
         program_error::ProgramError,
         pubkey::find_program_address,
-        sysvars::{rent::Rent, Sysvar},
+        sysvars::{
+            rent::{Rent, RENT_ID},
+            Sysvar,
+        },
         ProgramResult,
     },
 };
@@ -41,6 +49,11 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
+        check_account(admin, &[AccountCheck::Signer, AccountCheck::Writable])?;
+        check_account(global_pda, &[AccountCheck::Writable])?;
+        check_account(rent_sysvar, &[AccountCheck::Address(&RENT_ID)])?;
+        check_system_program(system_program)?;
+
         let (global_pda_key, global_bump) = find_program_address(&[b"global"], &crate::ID);
 
         if global_pda_key.ne(global_pda.key()) {
@@ -80,18 +93,22 @@ impl<'a> Initialize<'a> {
         .invoke_signed(&[pda_signer])?;
 
         //// initialize global pda data
-        let mut global_pda_data = self.accounts.global_pda.try_borrow_mut_data()?; // dev-improvements : what if i take AccountInfo in load*() to abstract away try_borrow* logic
-
-        let global: &mut Global = Global::load_mut(&mut global_pda_data)?;
+        let mut global_pda_data = self.accounts.global_pda.try_borrow_mut_data()?;
+        let global = Global::init(&mut global_pda_data)?;
 
         *global = Global {
             admin: *self.accounts.admin.key(),
             treasury: *self.accounts.global_pda.key(),
             challenge_ids: 1,
             bump: self.accounts.bump,
-            ..Global::default()
+            ..Global::new()
         };
 
+        Event::Initialized {
+            admin: *self.accounts.admin.key(),
+        }
+        .emit();
+
         Ok(())
     }
 }
@@ -0,0 +1,254 @@
+use {
+    crate::{
+        instructions::{
+            BatchSync, BatchSyncInstructionData, ClaimRewards, ClaimRewardsInstructionData,
+            ClaimWinnerPosition, ClaimWinnerPositionInstructionData, CreateChallenge,
+            CreateChallengeInstructionData, ExcuseDays, ExcuseDaysInstructionData, Initialize,
+            JoinChallenge, JoinChallengeInstructionData, MigrateAccount, SetOracle,
+            SetOracleInstructionData, SetSyncDelegate, SetSyncDelegateInstructionData, SettleDuel,
+            SettleDuelInstructionData, SyncLock, SyncLockInstructionData, TakeProfit,
+            TakeProfitInstructionData, ToggleChallengeCreation,
+            ToggleChallengeCreationInstructionData, UpdateAllowlist,
+            UpdateAllowlistInstructionData, View, ViewInstructionData, Withdraw,
+            WithdrawInstructionData,
+        },
+        ScreenWarErrors,
+    },
+    pinocchio::program_error::ProgramError,
+};
+
+// dev : one variant per discriminator, [discriminator, instruction_data..] on the wire
+#[derive(Debug, PartialEq)]
+pub enum ScreenWarsInstruction<'a> {
+    Initialize,
+    CreateChallenge(CreateChallengeInstructionData<'a>),
+    JoinChallenge(JoinChallengeInstructionData<'a>),
+    Withdraw(WithdrawInstructionData),
+    ClaimWinnerPosition(ClaimWinnerPositionInstructionData),
+    ToggleChallengeCreation(ToggleChallengeCreationInstructionData),
+    TakeProfit(TakeProfitInstructionData),
+    ClaimWinnerRewards(ClaimRewardsInstructionData),
+    ClaimCreatorRewards(ClaimRewardsInstructionData),
+    SyncLock(SyncLockInstructionData),
+    UpdateAllowlist(UpdateAllowlistInstructionData),
+    SettleDuel(SettleDuelInstructionData),
+    ClaimTeamRewards(ClaimRewardsInstructionData),
+    SetSyncDelegate(SetSyncDelegateInstructionData),
+    SetOracle(SetOracleInstructionData),
+    BatchSync(BatchSyncInstructionData<'a>),
+    MigrateAccount,
+    ViewParticipant(ViewInstructionData),
+    ViewPenalty(ViewInstructionData),
+    ViewRewards(ViewInstructionData),
+    ExcuseDays(ExcuseDaysInstructionData),
+}
+
+impl<'a> ScreenWarsInstruction<'a> {
+    pub fn unpack(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let (discriminator, data) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+
+        Ok(match discriminator {
+            Initialize::DISCRIMINATOR => Self::Initialize,
+            CreateChallenge::DISCRIMINATOR => Self::CreateChallenge(data.try_into()?),
+            JoinChallenge::DISCRIMINATOR => Self::JoinChallenge(data.try_into()?),
+            Withdraw::DISCRIMINATOR => Self::Withdraw(data.try_into()?),
+            ClaimWinnerPosition::DISCRIMINATOR => Self::ClaimWinnerPosition(data.try_into()?),
+            ToggleChallengeCreation::DISCRIMINATOR => {
+                Self::ToggleChallengeCreation(data.try_into()?)
+            }
+            TakeProfit::DISCRIMINATOR => Self::TakeProfit(data.try_into()?),
+            ClaimRewards::WINNER_REWARD_DISCRIMINATOR => Self::ClaimWinnerRewards(data.try_into()?),
+            ClaimRewards::CREATOR_REWARD_DISCRIMINATOR => {
+                Self::ClaimCreatorRewards(data.try_into()?)
+            }
+            SyncLock::DISCRIMINATOR => Self::SyncLock(data.try_into()?),
+            UpdateAllowlist::DISCRIMINATOR => Self::UpdateAllowlist(data.try_into()?),
+            SettleDuel::DISCRIMINATOR => Self::SettleDuel(data.try_into()?),
+            ClaimRewards::TEAM_REWARD_DISCRIMINATOR => Self::ClaimTeamRewards(data.try_into()?),
+            SetSyncDelegate::DISCRIMINATOR => Self::SetSyncDelegate(data.try_into()?),
+            SetOracle::DISCRIMINATOR => Self::SetOracle(data.try_into()?),
+            BatchSync::DISCRIMINATOR => Self::BatchSync(data.try_into()?),
+            MigrateAccount::DISCRIMINATOR => Self::MigrateAccount,
+            View::PARTICIPANT_DISCRIMINATOR => Self::ViewParticipant(data.try_into()?),
+            View::PENALTY_DISCRIMINATOR => Self::ViewPenalty(data.try_into()?),
+            View::REWARDS_DISCRIMINATOR => Self::ViewRewards(data.try_into()?),
+            ExcuseDays::DISCRIMINATOR => Self::ExcuseDays(data.try_into()?),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    pub fn discriminator(&self) -> u8 {
+        *match self {
+            Self::Initialize => Initialize::DISCRIMINATOR,
+            Self::CreateChallenge(_) => CreateChallenge::DISCRIMINATOR,
+            Self::JoinChallenge(_) => JoinChallenge::DISCRIMINATOR,
+            Self::Withdraw(_) => Withdraw::DISCRIMINATOR,
+            Self::ClaimWinnerPosition(_) => ClaimWinnerPosition::DISCRIMINATOR,
+            Self::ToggleChallengeCreation(_) => ToggleChallengeCreation::DISCRIMINATOR,
+            Self::TakeProfit(_) => TakeProfit::DISCRIMINATOR,
+            Self::ClaimWinnerRewards(_) => ClaimRewards::WINNER_REWARD_DISCRIMINATOR,
+            Self::ClaimCreatorRewards(_) => ClaimRewards::CREATOR_REWARD_DISCRIMINATOR,
+            Self::SyncLock(_) => SyncLock::DISCRIMINATOR,
+            Self::UpdateAllowlist(_) => UpdateAllowlist::DISCRIMINATOR,
+            Self::SettleDuel(_) => SettleDuel::DISCRIMINATOR,
+            Self::ClaimTeamRewards(_) => ClaimRewards::TEAM_REWARD_DISCRIMINATOR,
+            Self::SetSyncDelegate(_) => SetSyncDelegate::DISCRIMINATOR,
+            Self::SetOracle(_) => SetOracle::DISCRIMINATOR,
+            Self::BatchSync(_) => BatchSync::DISCRIMINATOR,
+            Self::MigrateAccount => MigrateAccount::DISCRIMINATOR,
+            Self::ViewParticipant(_) => View::PARTICIPANT_DISCRIMINATOR,
+            Self::ViewPenalty(_) => View::PENALTY_DISCRIMINATOR,
+            Self::ViewRewards(_) => View::REWARDS_DISCRIMINATOR,
+            Self::ExcuseDays(_) => ExcuseDays::DISCRIMINATOR,
+        }
+    }
+
+    // dev : exact number of bytes pack() writes, lets clients size their buffer
+    pub fn packed_len(&self) -> usize {
+        let mut counter = PackWriter {
+            buffer: &mut [],
+            len: 0,
+            overflowed: false,
+        };
+        self.write(&mut counter);
+        counter.len
+    }
+
+    // dev : returns the number of bytes written into buffer
+    pub fn pack(&self, buffer: &mut [u8]) -> Result<usize, ProgramError> {
+        let mut writer = PackWriter {
+            buffer,
+            len: 0,
+            overflowed: false,
+        };
+        self.write(&mut writer);
+
+        if writer.overflowed {
+            return Err(ScreenWarErrors::IntegerBoundsExceeded.into());
+        }
+
+        Ok(writer.len)
+    }
+
+    fn write(&self, writer: &mut PackWriter) {
+        writer.put(&[self.discriminator()]);
+
+        match self {
+            Self::Initialize => {}
+            Self::CreateChallenge(data) => {
+                writer.put(&data.start_time.to_le_bytes());
+                writer.put(&data.daily_timer.to_le_bytes());
+                // dev : earlier options are written as None tags so later ones keep their position
+                let has_token_gate = data.token_gate.is_some();
+                let has_duel = data.duel.is_some();
+                let has_team_count = data.team_count.is_some();
+                let has_extensions = !data.extensions.is_empty();
+
+                match &data.allowlist_root {
+                    Some(root) => writer.put_some(root),
+                    None if has_token_gate || has_duel || has_team_count || has_extensions => {
+                        writer.put(&[0])
+                    }
+                    None => {}
+                }
+                match &data.token_gate {
+                    Some(gate) => {
+                        writer.put_some(&gate.mint);
+                        writer.put(&gate.min_balance.to_le_bytes());
+                    }
+                    None if has_duel || has_team_count || has_extensions => writer.put(&[0]),
+                    None => {}
+                }
+                match &data.duel {
+                    Some(duel) => {
+                        writer.put_some(&duel.opponent);
+                        writer.put(&duel.stake.to_le_bytes());
+                    }
+                    None if has_team_count || has_extensions => writer.put(&[0]),
+                    None => {}
+                }
+                match data.team_count {
+                    Some(team_count) => writer.put_some(&[team_count]),
+                    None if has_extensions => writer.put(&[0]),
+                    None => {}
+                }
+                writer.put(data.extensions);
+            }
+            Self::JoinChallenge(data) => {
+                writer.put(&data.challenge_id.to_le_bytes());
+                writer.put(&[data.team_id, data.prepay as u8]);
+                writer.put(&data.device);
+                writer.put(data.allowlist_proof);
+            }
+            Self::Withdraw(data) => writer.put(&data.challenge_id.to_le_bytes()),
+            Self::ClaimWinnerPosition(data) => writer.put(&data.challenge_id.to_le_bytes()),
+            Self::ToggleChallengeCreation(data) => writer.put(&[data.pause as u8]),
+            Self::TakeProfit(data) => writer.put(&data.amount.to_le_bytes()),
+            Self::ClaimWinnerRewards(data)
+            | Self::ClaimCreatorRewards(data)
+            | Self::ClaimTeamRewards(data) => writer.put(&data.challenge_id.to_le_bytes()),
+            Self::SyncLock(data) => {
+                writer.put(&data.challenge_id.to_le_bytes());
+                match &data.debug_data {
+                    Some(debug) => writer.put_some(&[
+                        debug.user_passed as u8,
+                        debug.days_not_synced,
+                        debug.synced_today as u8,
+                    ]),
+                    None => writer.put(&[0, 0, 0, 0]),
+                }
+            }
+            Self::UpdateAllowlist(data) => {
+                writer.put(&data.challenge_id.to_le_bytes());
+                writer.put(&data.allowlist_root);
+            }
+            Self::SettleDuel(data) => writer.put(&data.challenge_id.to_le_bytes()),
+            Self::SetSyncDelegate(data) => {
+                writer.put(&data.delegate);
+                writer.put(&data.delegate_expiry.to_le_bytes());
+            }
+            Self::SetOracle(data) => writer.put(&data.oracle),
+            Self::BatchSync(data) => {
+                writer.put(&data.challenge_id.to_le_bytes());
+                writer.put(data.results);
+            }
+            Self::MigrateAccount => {}
+            Self::ViewParticipant(data) | Self::ViewRewards(data) => {
+                writer.put(&data.challenge_id.to_le_bytes())
+            }
+            Self::ViewPenalty(data) => {
+                writer.put(&data.challenge_id.to_le_bytes());
+                writer.put(&[data.days_missed]);
+            }
+            Self::ExcuseDays(data) => {
+                writer.put(&data.challenge_id.to_le_bytes());
+                writer.put(&data.start.to_le_bytes());
+                writer.put(&data.end.to_le_bytes());
+            }
+        }
+    }
+}
+
+// dev : counts every byte, copies only while the buffer has room
+struct PackWriter<'b> {
+    buffer: &'b mut [u8],
+    len: usize,
+    overflowed: bool,
+}
+
+impl PackWriter<'_> {
+    fn put(&mut self, bytes: &[u8]) {
+        match self.buffer.get_mut(self.len..self.len + bytes.len()) {
+            Some(slot) => slot.copy_from_slice(bytes),
+            None => self.overflowed = true,
+        }
+        self.len += bytes.len();
+    }
+
+    fn put_some(&mut self, bytes: &[u8]) {
+        self.put(&[1]);
+        self.put(bytes);
+    }
+}
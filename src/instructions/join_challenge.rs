@@ -1,20 +1,32 @@
-use crate::ScreenWarErrors;
+use crate::{
+    events::Event,
+    instructions::SyncLock,
+    utils::{
+        check_account, check_system_program, is_valid_merkle_proof_len, verify_merkle_proof,
+        AccountCheck, Decoder, TokenAccountView,
+    },
+    ScreenWarErrors,
+};
 pub use {
-    crate::state::{Challenge, User},
+    crate::state::{Challenge, Lifecycle, Team, User},
     pinocchio::{
         account_info::AccountInfo,
         instruction::{Seed, Signer},
         program_error::ProgramError,
-        pubkey::find_program_address,
-        sysvars::{clock::Clock, rent::Rent, Sysvar},
+        pubkey::{find_program_address, Pubkey},
+        sysvars::{
+            clock::Clock,
+            rent::{Rent, RENT_ID},
+            Sysvar,
+        },
         ProgramResult,
     },
-    pinocchio_system::instructions::CreateAccount,
+    pinocchio_system::instructions::{CreateAccount, Transfer},
 };
 
 pub struct JoinChallenge<'a> {
     pub accounts: JoinChallengeAccounts<'a>,
-    pub instruction_data: JoinChallengeInstructionData,
+    pub instruction_data: JoinChallengeInstructionData<'a>,
 }
 
 pub struct JoinChallengeAccounts<'a> {
@@ -23,72 +35,96 @@ pub struct JoinChallengeAccounts<'a> {
     pub user_pda: &'a AccountInfo,
     pub rent_sysvar: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
+    // dev : optional accounts in fixed order, each present only when the challenge requires it
+    // [gate_token_account (token gated), team_pda (team challenge)]
+    pub remaining_accounts: &'a [AccountInfo],
     pub user_pda_bump: u8,
 }
-pub struct JoinChallengeInstructionData {
+#[derive(Debug, PartialEq)]
+pub struct JoinChallengeInstructionData<'a> {
     pub challenge_id: u32,
+    pub team_id: u8,               // dev : 0 when joining an individual challenge
+    pub prepay: bool,              // dev : escrow the full challenge stake in user_pda upfront
+    pub device: Pubkey,            // dev : key that signs daily reports, default pubkey means none
+    pub allowlist_proof: &'a [u8], // dev : only checked for private challenges, 32 byte sibling nodes
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for JoinChallengeAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [user, challenge, user_pda, rent_sysvar, system_program] = accounts else {
+        let [user, challenge, user_pda, rent_sysvar, system_program, remaining @ ..] = accounts
+        else {
             return Err(ProgramError::NotEnoughAccountKeys)?;
         };
 
+        check_account(user, &[AccountCheck::Signer, AccountCheck::Writable])?;
+        check_account(challenge, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(user_pda, &[AccountCheck::Writable])?;
+        check_account(rent_sysvar, &[AccountCheck::Address(&RENT_ID)])?;
+        check_system_program(system_program)?;
+
         let (user_pda_key, user_pda_bump) =
             find_program_address(&[b"user", user.key().as_slice()], &crate::ID);
 
-        if (&user_pda_key).ne(user_pda.key()) {
+        if user_pda_key.ne(user_pda.key()) {
             return Err(ScreenWarErrors::InvalidUserPDA.into());
         };
 
-        // dev : Safer -> no need for signer validations or init_if checks
-
         Ok(Self {
             user,
             challenge,
             user_pda,
             rent_sysvar,
             system_program,
+            remaining_accounts: remaining,
             user_pda_bump,
         })
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for JoinChallengeInstructionData {
+impl<'a> TryFrom<&'a [u8]> for JoinChallengeInstructionData<'a> {
     type Error = ProgramError;
 
     fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
-        if instruction_data.len().ne(&4usize) {
+        // dev
+        // first 4 bytes would be challenge_id
+        // next byte would be team_id, 0 for individual challenges
+        // next byte would be prepay : [flag]
+        // next 32 bytes would be device, default pubkey for none
+        // remaining bytes would be the merkle proof of the joining wallet
+        // dev : every field before the proof is always encoded, so a proof byte is never read as a field
+        let mut data = Decoder::new(instruction_data);
+        let challenge_id = data.u32()?;
+        let team_id = data.u8()?;
+        let prepay = data.bool()?;
+        let device = data.pubkey()?;
+        let allowlist_proof = data.rest();
+
+        if !is_valid_merkle_proof_len(allowlist_proof) {
             return Err(ProgramError::InvalidInstructionData);
         }
 
-        let challenge_id = u32::from_le_bytes(instruction_data.try_into().unwrap());
-
-        Ok(Self { challenge_id })
+        Ok(Self {
+            challenge_id,
+            team_id,
+            prepay,
+            device,
+            allowlist_proof,
+        })
     }
 }
 
-impl<'a> TryFrom<(&'a [AccountInfo], &'a [u8])> for JoinChallenge<'a> {
+impl<'a> TryFrom<(&'a [AccountInfo], JoinChallengeInstructionData<'a>)> for JoinChallenge<'a> {
     type Error = ProgramError;
 
     fn try_from(
-        (accounts, instruction_data): (&'a [AccountInfo], &'a [u8]),
+        (accounts, instruction_data): (&'a [AccountInfo], JoinChallengeInstructionData<'a>),
     ) -> Result<Self, Self::Error> {
         let accounts = JoinChallengeAccounts::try_from(accounts)?;
-        let instruction_data = JoinChallengeInstructionData::try_from(instruction_data)?;
 
         // validate correct challenge pda
-        let (challenge_pda_key, _) = find_program_address(
-            &[b"challenge", &instruction_data.challenge_id.to_le_bytes()],
-            &crate::ID,
-        );
-
-        if challenge_pda_key.ne(accounts.challenge.key()) {
-            return Err(ScreenWarErrors::InvalidChallengePDA.into());
-        }
+        Challenge::validate_pda(accounts.challenge, instruction_data.challenge_id)?;
 
         // return Self
         Ok(Self {
@@ -102,15 +138,55 @@ impl<'a> JoinChallenge<'a> {
     pub const DISCRIMINATOR: &'a u8 = &2;
 
     pub fn process(&mut self) -> ProgramResult {
-        let mut challenge_ptr = self.accounts.challenge.try_borrow_mut_data()?;
-        let challenge: &mut Challenge = Challenge::load_mut(&mut challenge_ptr)?;
+        let mut challenge = Challenge::from_account_info_mut(self.accounts.challenge)?;
+        let duel_stake = if challenge.is_duel {
+            challenge.duel_stake
+        } else {
+            0
+        };
+        let prepaid_stake = if self.instruction_data.prepay {
+            Self::calculate_full_stake(challenge.start, challenge.end)?
+        } else {
+            0
+        };
+        let mut remaining_accounts = self.accounts.remaining_accounts.iter();
 
         //// validate challenge has not started
         let now = Clock::get()?.unix_timestamp;
 
-        if now > challenge.start {
-            return Err(ScreenWarErrors::JoinedLate.into());
-        };
+        challenge
+            .lifecycle(now)?
+            .validate(&[Lifecycle::Pending])
+            .map_err(|_| ScreenWarErrors::JoinedLate)?;
+
+        //// validate wallet is allowlisted for private challenges
+        if challenge.is_private
+            && !verify_merkle_proof(
+                &challenge.allowlist_root,
+                self.accounts.user.key(),
+                self.instruction_data.allowlist_proof,
+            )
+        {
+            return Err(ScreenWarErrors::NotAllowlisted.into());
+        }
+
+        //// validate wallet holds enough of the gate mint for token gated challenges
+        if challenge.gate_mint.ne(&Pubkey::default()) {
+            Self::validate_token_gate(
+                self.accounts.user,
+                remaining_accounts.next(),
+                &challenge.gate_mint,
+                challenge.gate_min_balance,
+            )?;
+        }
+
+        //// validate only the creator and invited opponent can enter a duel
+        if challenge.is_duel {
+            Self::validate_duelist(self.accounts.user, challenge.creator, challenge.opponent)?;
+        }
+
+        //// validate team choice matches challenge mode
+        Self::validate_team_id(challenge.team_count, self.instruction_data.team_id)?;
 
         ///// increment challenge participants
         challenge.total_participants = challenge
@@ -118,6 +194,19 @@ impl<'a> JoinChallenge<'a> {
             .checked_add(1)
             .ok_or(ProgramError::ArithmeticOverflow)?;
 
+        // dev : challenge borrow must be released before the stake transfer cpi touches its lamports
+        drop(challenge);
+
+        //// escrow duel stake in the challenge pda
+        if duel_stake > 0 {
+            Transfer {
+                from: self.accounts.user,
+                to: self.accounts.challenge,
+                lamports: duel_stake,
+            }
+            .invoke()?;
+        }
+
         //// create and initialize user_pda
         let space = User::LEN;
         let rent = Rent::get()?.minimum_balance(space);
@@ -139,15 +228,164 @@ impl<'a> JoinChallenge<'a> {
         .invoke_signed(&[pda_signature])?;
 
         let mut user_pda_ptr = self.accounts.user_pda.try_borrow_mut_data()?;
-        let user_pda = User::load_mut(&mut user_pda_ptr)?;
+        let user_pda = User::init(&mut user_pda_ptr)?;
 
         *user_pda = User {
             user: *self.accounts.user.key(),
             challenge_id: self.instruction_data.challenge_id,
+            team_id: self.instruction_data.team_id,
+            prepaid_balance: prepaid_stake,
             bump: self.accounts.user_pda_bump,
-            ..User::default()
+            device: self.instruction_data.device,
+            ..User::new()
+        };
+
+        drop(user_pda_ptr);
+
+        //// escrow prepaid stake in user_pda, SyncLock draws daily lamports from it
+        if prepaid_stake > 0 {
+            Transfer {
+                from: self.accounts.user,
+                to: self.accounts.user_pda,
+                lamports: prepaid_stake,
+            }
+            .invoke()?;
+        }
+
+        //// enroll in team, first member pays for the team pda
+        if self.instruction_data.team_id > 0 {
+            Self::join_team(
+                self.accounts.user,
+                remaining_accounts.next(),
+                self.instruction_data.challenge_id,
+                self.instruction_data.team_id,
+            )?;
+        }
+
+        Event::ChallengeJoined {
+            challenge_id: self.instruction_data.challenge_id,
+            user: *self.accounts.user.key(),
+            team_id: self.instruction_data.team_id,
+            prepaid_balance: prepaid_stake,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    // dev : one DAILY_LAMPORTS deposit for every day of the challenge
+    pub fn calculate_full_stake(start: i64, end: i64) -> Result<u64, ProgramError> {
+        let one_day = 24 * 60 * 60;
+        let days = end
+            .checked_sub(start)
+            .ok_or(ScreenWarErrors::IntegerUnderflow)?
+            / one_day;
+
+        (days as u64)
+            .checked_mul(SyncLock::DAILY_LAMPORTS)
+            .ok_or(ScreenWarErrors::IntegerOverflow.into())
+    }
+
+    pub fn validate_team_id(team_count: u8, team_id: u8) -> ProgramResult {
+        let valid = if team_count == 0 {
+            team_id == 0
+        } else {
+            (1..=team_count).contains(&team_id)
         };
 
+        if !valid {
+            return Err(ScreenWarErrors::InvalidTeam.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn join_team(
+        user: &AccountInfo,
+        team_pda: Option<&AccountInfo>,
+        challenge_id: u32,
+        team_id: u8,
+    ) -> ProgramResult {
+        let team_pda = team_pda.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        let id_binding = challenge_id.to_le_bytes();
+        let team_id_binding = [team_id];
+        let (team_pda_key, team_bump) =
+            find_program_address(&[b"team", &id_binding, &team_id_binding], &crate::ID);
+
+        if team_pda_key.ne(team_pda.key()) {
+            return Err(ScreenWarErrors::InvalidTeamPDA.into());
+        }
+
+        if !team_pda.is_owned_by(&crate::ID) {
+            let space = Team::LEN;
+            let rent = Rent::get()?.minimum_balance(space);
+            let bump_binding = [team_bump];
+            let seeds = &[
+                Seed::from(b"team"),
+                Seed::from(&id_binding),
+                Seed::from(&team_id_binding),
+                Seed::from(&bump_binding),
+            ];
+            let pda_signature = Signer::from(seeds);
+
+            CreateAccount {
+                from: user,
+                to: team_pda,
+                lamports: rent,
+                space: space as u64,
+                owner: &crate::ID,
+            }
+            .invoke_signed(&[pda_signature])?;
+
+            let mut team_pda_ptr = team_pda.try_borrow_mut_data()?;
+            *Team::init(&mut team_pda_ptr)? = Team {
+                challenge_id,
+                team_id,
+                bump: team_bump,
+                ..Team::new()
+            };
+        }
+
+        let mut team = Team::from_account_info_mut(team_pda)?;
+
+        team.members = team
+            .members
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn validate_duelist(
+        user: &AccountInfo,
+        creator: Pubkey,
+        opponent: Pubkey,
+    ) -> ProgramResult {
+        if user.key().ne(&creator) && user.key().ne(&opponent) {
+            return Err(ScreenWarErrors::NotDuelist.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_token_gate(
+        user: &AccountInfo,
+        gate_token_account: Option<&AccountInfo>,
+        gate_mint: &Pubkey,
+        gate_min_balance: u64,
+    ) -> ProgramResult {
+        let token_account =
+            TokenAccountView::load(gate_token_account.ok_or(ProgramError::NotEnoughAccountKeys)?)?;
+
+        if token_account.mint.ne(gate_mint) || token_account.owner.ne(user.key()) {
+            return Err(ScreenWarErrors::InvalidTokenAccount.into());
+        }
+
+        if token_account.amount < gate_min_balance {
+            return Err(ScreenWarErrors::TokenGateNotMet.into());
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,151 @@
+use {
+    crate::{
+        events::Event,
+        state::{write_account, Challenge, Global, Lifecycle, User},
+        utils::{check_account, check_system_program, AccountCheck},
+        ScreenWarErrors,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::find_program_address,
+        sysvars::{clock::Clock, rent::Rent, Sysvar},
+        ProgramResult,
+    },
+};
+
+use pinocchio_system::instructions::Transfer;
+
+pub struct MigrateAccount<'a> {
+    pub accounts: MigrateAccountAccounts<'a>,
+}
+
+pub struct MigrateAccountAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub account: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub challenge: Option<&'a AccountInfo>, // dev : required only for users, their enrolled challenge
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MigrateAccount<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigrateAccountAccounts::try_from(accounts)?;
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MigrateAccountAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [payer, account, system_program, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // dev : permissionless, the rewrite is deterministic and payer only funds the extra rent
+        check_account(payer, &[AccountCheck::Signer, AccountCheck::Writable])?;
+
+        // dev : a non program account can not be resized by us, reject it before reading its length
+        check_account(account, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_system_program(system_program)?;
+
+        Ok(Self {
+            payer,
+            account,
+            system_program,
+            challenge: remaining.first(),
+        })
+    }
+}
+
+impl<'a> MigrateAccount<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &16;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let account = self.accounts.account;
+
+        // dev : every older layout has a distinct length, so the length alone says what to upgrade
+        match account.data_len() {
+            Global::V0_LEN => self.rewrite(Global::from_account_info(account)?)?,
+            Challenge::V0_LEN => {
+                let mut challenge = Challenge::from_account_info(account)?;
+                challenge.bump =
+                    self.derive_bump(&[b"challenge", &{ challenge.challenge_id }.to_le_bytes()])?;
+                self.rewrite(challenge)?
+            }
+            User::V0_LEN | User::V1_LEN | User::V2_LEN => {
+                let mut user = User::from_account_info(account)?;
+                if account.data_len() == User::V0_LEN {
+                    user.bump = self.derive_bump(&[b"user", user.user.as_slice()])?;
+                }
+                user.next_sync_day = self.next_sync_day(&user)?;
+                self.rewrite(user)?
+            }
+            _ => return Err(ScreenWarErrors::AccountAlreadyMigrated.into()),
+        }
+
+        let header = account.try_borrow_data()?;
+
+        Event::AccountMigrated {
+            account: *account.key(),
+            discriminator: header[0],
+            version: header[1],
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    // dev : next_sync_day was not tracked before v3, legacy syncs left no record of the days they settled
+    // so a participant of a started challenge resumes at today, before the start nothing was synced
+    fn next_sync_day(&self, user: &User) -> Result<u32, ProgramError> {
+        let challenge = self
+            .accounts
+            .challenge
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        Challenge::validate_pda(challenge, user.challenge_id)?;
+
+        let challenge = Challenge::from_account_info(challenge)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        match challenge.lifecycle(now)? {
+            Lifecycle::Pending => Ok(0),
+            _ => challenge.day(now),
+        }
+    }
+
+    // dev : v0 challenges and users were allocated too short to hold their bump, the search also
+    // proves the account sits at the address its own fields derive
+    fn derive_bump(&self, seeds: &[&[u8]]) -> Result<u8, ProgramError> {
+        let (pda, bump) = find_program_address(seeds, &crate::ID);
+        if pda.ne(self.accounts.account.key()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(bump)
+    }
+
+    // dev : upgraded is already in the current layout, only the account size and rent lag behind
+    fn rewrite<T: Copy>(&self, upgraded: T) -> ProgramResult {
+        let account = self.accounts.account;
+        let space = core::mem::size_of::<T>();
+
+        //// top up rent for the grown layout
+        let rent = Rent::get()?.minimum_balance(space);
+        let shortfall = rent.saturating_sub(account.lamports());
+        if shortfall > 0 {
+            Transfer {
+                from: self.accounts.payer,
+                to: account,
+                lamports: shortfall,
+            }
+            .invoke()?;
+        }
+
+        account.resize(space)?;
+
+        write_account(&mut account.try_borrow_mut_data()?, upgraded)
+    }
+}
@@ -1,14 +1,25 @@
+pub mod batch_sync;
 pub mod claim_winner_position;
 pub mod create_challenge;
+pub mod excuse_days;
 pub mod initialize;
+pub mod instruction;
 pub mod join_challenge;
+pub mod migrate_account;
 pub mod rewards;
+pub mod set_oracle;
+pub mod set_sync_delegate;
+pub mod settle_duel;
 pub mod sync_lock;
 pub mod take_profit;
 pub mod toggle_challenge_creation;
+pub mod update_allowlist;
+pub mod view;
 pub mod withdraw;
 
 pub use {
-    claim_winner_position::*, create_challenge::*, initialize::*, join_challenge::*, rewards::*,
-    sync_lock::*, take_profit::*, toggle_challenge_creation::*, withdraw::*,
+    batch_sync::*, claim_winner_position::*, create_challenge::*, excuse_days::*, initialize::*,
+    instruction::*, join_challenge::*, migrate_account::*, rewards::*, set_oracle::*,
+    set_sync_delegate::*, settle_duel::*, sync_lock::*, take_profit::*,
+    toggle_challenge_creation::*, update_allowlist::*, view::*, withdraw::*,
 };
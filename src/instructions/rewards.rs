@@ -1,17 +1,20 @@
 use {
     crate::{
-        state::{Challenge, Global},
+        events::{Event, RewardKind},
+        state::{Challenge, Global, Lifecycle, Team, User},
+        utils::{check_account, check_system_program, AccountCheck, Decoder},
         ScreenWarErrors,
     },
     pinocchio::{
         account_info::AccountInfo,
-        instruction::{Seed, Signer},
         program_error::ProgramError,
-        pubkey::{find_program_address, Pubkey},
-        sysvars::{clock::Clock, Sysvar},
+        pubkey::Pubkey,
+        sysvars::{
+            clock::{Clock, CLOCK_ID},
+            Sysvar,
+        },
         ProgramResult,
     },
-    pinocchio_system::instructions::Transfer,
 };
 
 pub struct ClaimRewards<'a> {
@@ -25,31 +28,26 @@ pub struct ClaimRewardsAccounts<'a> {
     pub challenge: &'a AccountInfo,
     pub clock_sysvar: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
+    // dev : [user_pda, team_pda], required only for team reward claims
+    pub remaining_accounts: &'a [AccountInfo],
     pub global_bump: u8,
 }
 
+#[derive(Debug, PartialEq)]
 pub struct ClaimRewardsInstructionData {
     pub challenge_id: u32,
 }
 
-impl<'a> TryFrom<(&'a [AccountInfo], &'a [u8])> for ClaimRewards<'a> {
+impl<'a> TryFrom<(&'a [AccountInfo], ClaimRewardsInstructionData)> for ClaimRewards<'a> {
     type Error = ProgramError;
 
     fn try_from(
-        (accounts, instruction_data): (&'a [AccountInfo], &'a [u8]),
+        (accounts, instruction_data): (&'a [AccountInfo], ClaimRewardsInstructionData),
     ) -> Result<Self, Self::Error> {
         let accounts = ClaimRewardsAccounts::try_from(accounts)?;
-        let instruction_data = ClaimRewardsInstructionData::try_from(instruction_data)?;
 
         // validate correct challenge pda
-        let (challenge_pda_key, _) = find_program_address(
-            &[b"challenge", &instruction_data.challenge_id.to_le_bytes()],
-            &crate::ID,
-        );
-
-        if challenge_pda_key.ne(accounts.challenge.key()) {
-            return Err(ScreenWarErrors::InvalidChallengePDA.into());
-        }
+        Challenge::validate_pda(accounts.challenge, instruction_data.challenge_id)?;
 
         // return Self
         Ok(Self {
@@ -63,18 +61,18 @@ impl<'a> TryFrom<&'a [AccountInfo]> for ClaimRewardsAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [user, global, challenge, clock_sysvar, system_program] = accounts else {
+        let [user, global, challenge, clock_sysvar, system_program, remaining @ ..] = accounts
+        else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        if !user.is_signer() {
-            return Err(ScreenWarErrors::NotSigner.into());
-        }
+        check_account(user, &[AccountCheck::Signer, AccountCheck::Writable])?;
+        check_account(global, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(challenge, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(clock_sysvar, &[AccountCheck::Address(&CLOCK_ID)])?;
+        check_system_program(system_program)?;
 
-        let (global_pda_key, global_bump) = find_program_address(&[b"global"], &crate::ID);
-        if global.key().ne(&global_pda_key) {
-            return Err(ProgramError::InvalidSeeds);
-        };
+        let global_bump = Global::validate_pda(global)?;
 
         Ok(Self {
             user,
@@ -82,6 +80,7 @@ impl<'a> TryFrom<&'a [AccountInfo]> for ClaimRewardsAccounts<'a> {
             challenge,
             clock_sysvar,
             system_program,
+            remaining_accounts: remaining,
             global_bump,
         })
     }
@@ -91,11 +90,9 @@ impl<'a> TryFrom<&'a [u8]> for ClaimRewardsInstructionData {
     type Error = ProgramError;
 
     fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
-        if instruction_data.len().ne(&4usize) {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        let challenge_id = u32::from_le_bytes(instruction_data.try_into().unwrap());
+        let mut data = Decoder::new(instruction_data);
+        let challenge_id = data.u32()?;
+        data.finish()?;
 
         Ok(Self { challenge_id })
     }
@@ -104,68 +101,174 @@ impl<'a> TryFrom<&'a [u8]> for ClaimRewardsInstructionData {
 impl<'a> ClaimRewards<'a> {
     pub const WINNER_REWARD_DISCRIMINATOR: &'a u8 = &7;
     pub const CREATOR_REWARD_DISCRIMINATOR: &'a u8 = &8;
+    pub const TEAM_REWARD_DISCRIMINATOR: &'a u8 = &12;
 
     pub fn process_winner_rewards(&mut self) -> ProgramResult {
         // get mutable references to global and challenge pdas
-        let mut challenge_raw_data = self.accounts.challenge.try_borrow_mut_data()?;
-        let mut challenge = Challenge::load_mut(&mut challenge_raw_data)?;
+        let mut challenge = Challenge::from_account_info_mut(self.accounts.challenge)?;
 
-        let mut global_raw_data = self.accounts.global.try_borrow_mut_data()?;
-        let global = Global::load_mut(&mut global_raw_data)?;
+        let mut global = Global::from_account_info_mut(self.accounts.global)?;
 
+        Self::validate_duel_is_settled(&challenge)?;
         Self::validate_caller_is_winner(self.accounts.user, challenge.winner)?;
-        Self::validate_contention_period_is_over(challenge.end)?;
+        let now = Clock::get()?.unix_timestamp;
+        let lifecycle = challenge.lifecycle(now)?;
+        lifecycle.validate(&[Lifecycle::Settlement])?;
 
         let (winner_rewards, _, treasury_profits) =
             Self::calculate_rewards(challenge.total_slashed)?;
 
-        let claimed_by_creator = challenge.creator_has_claimed;
-        if claimed_by_creator {
-            Self::close_challenge_account(&mut challenge)?;
-        } else {
-            Self::update_treasury_profits(global, treasury_profits)?;
+        // dev : whichever share is claimed first books the treasury share, the challenge pda outlives
+        // both claims since withdrawals and team claims still read it once Closed
+        if !challenge.creator_has_claimed {
+            Self::update_treasury_profits(&mut global, treasury_profits)?;
         }
 
         // dev : winner state is nullified with default pubkey after claiming to prevent fund draining
-        Self::set_winner_claimed(challenge)?;
-        Self::transfer_rewards(
-            self.accounts.global,
-            self.accounts.user,
-            winner_rewards,
-            self.accounts.global_bump,
-        )?;
+        Self::set_winner_claimed(&mut challenge)?;
+        lifecycle.validate_transition(challenge.lifecycle(now)?)?;
+
+        drop(global);
+        Self::transfer_rewards(self.accounts.global, self.accounts.user, winner_rewards)?;
+
+        Event::RewardsClaimed {
+            challenge_id: self.instruction_data.challenge_id,
+            claimer: *self.accounts.user.key(),
+            kind: RewardKind::Winner,
+            amount: winner_rewards,
+        }
+        .emit();
+
         Ok(())
     }
 
     pub fn process_creator_rewards(&mut self) -> ProgramResult {
         // get mutable references to global and challenge pdas
-        let mut challenge_raw_data = self.accounts.challenge.try_borrow_mut_data()?;
-        let mut challenge = Challenge::load_mut(&mut challenge_raw_data)?;
+        let mut challenge = Challenge::from_account_info_mut(self.accounts.challenge)?;
 
-        let mut global_raw_data = self.accounts.global.try_borrow_mut_data()?;
-        let global = Global::load_mut(&mut global_raw_data)?;
+        let mut global = Global::from_account_info_mut(self.accounts.global)?;
 
+        Self::validate_duel_is_settled(&challenge)?;
         Self::validate_caller_is_creator(self.accounts.user, challenge.creator)?;
-        Self::validate_contention_period_is_over(challenge.end)?;
+        let now = Clock::get()?.unix_timestamp;
+        let lifecycle = challenge.lifecycle(now)?;
+        lifecycle.validate(&[Lifecycle::Settlement])?;
 
         let (_, creator_rewards, treasury_profits) =
             Self::calculate_rewards(challenge.total_slashed)?;
 
-        let claimed_by_winner = challenge.winner_has_claimed;
-        if claimed_by_winner {
-            Self::close_challenge_account(&mut challenge)?;
-        } else {
-            Self::update_treasury_profits(global, treasury_profits)?;
+        if !challenge.winner_has_claimed {
+            Self::update_treasury_profits(&mut global, treasury_profits)?;
         }
 
         // dev : creator state is nullified with default pubkey after claiming to prevent fund draining
-        Self::set_creator_claimed(challenge)?;
-        Self::transfer_rewards(
-            self.accounts.global,
-            self.accounts.user,
-            creator_rewards,
-            self.accounts.global_bump,
-        )?;
+        Self::set_creator_claimed(&mut challenge)?;
+        lifecycle.validate_transition(challenge.lifecycle(now)?)?;
+
+        drop(global);
+        Self::transfer_rewards(self.accounts.global, self.accounts.user, creator_rewards)?;
+
+        Event::RewardsClaimed {
+            challenge_id: self.instruction_data.challenge_id,
+            claimer: *self.accounts.user.key(),
+            kind: RewardKind::Creator,
+            amount: creator_rewards,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    pub fn process_team_rewards(&mut self) -> ProgramResult {
+        let [user_pda, team_pda, ..] = self.accounts.remaining_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        check_account(user_pda, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(team_pda, &[AccountCheck::Owned])?;
+
+        // get mutable references to global, challenge and user pdas
+        let mut challenge = Challenge::from_account_info_mut(self.accounts.challenge)?;
+
+        let mut global = Global::from_account_info_mut(self.accounts.global)?;
+
+        Self::validate_team_pdas(self.accounts.user, user_pda, team_pda, &challenge)?;
+
+        let mut user_pda = User::from_account_info_mut(user_pda)?;
+
+        let team = Team::from_account_info(team_pda)?;
+
+        Self::validate_caller_is_winning_team_member(&challenge, &user_pda)?;
+        // dev : members keep claiming after the winner and creator shares closed the challenge
+        let now = Clock::get()?.unix_timestamp;
+        let lifecycle = challenge.lifecycle(now)?;
+        lifecycle.validate(&[Lifecycle::Settlement, Lifecycle::Closed])?;
+
+        let (winner_rewards, _, treasury_profits) =
+            Self::calculate_rewards(challenge.total_slashed)?;
+
+        let member_rewards = winner_rewards
+            .checked_div(team.members as u64)
+            .ok_or(ScreenWarErrors::IntegerUnderflow)?;
+
+        // dev : first member to claim books the treasury share, unless creator already did
+        if !challenge.winner_has_claimed {
+            if !challenge.creator_has_claimed {
+                Self::update_treasury_profits(&mut global, treasury_profits)?;
+            }
+            challenge.winner_has_claimed = true;
+        }
+        lifecycle.validate_transition(challenge.lifecycle(now)?)?;
+
+        user_pda.team_reward_claimed = true;
+
+        drop(global);
+        Self::transfer_rewards(self.accounts.global, self.accounts.user, member_rewards)?;
+
+        Event::RewardsClaimed {
+            challenge_id: self.instruction_data.challenge_id,
+            claimer: *self.accounts.user.key(),
+            kind: RewardKind::TeamMember,
+            amount: member_rewards,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    pub fn validate_team_pdas(
+        caller: &AccountInfo,
+        user_pda: &AccountInfo,
+        team_pda: &AccountInfo,
+        challenge: &Challenge,
+    ) -> ProgramResult {
+        if challenge.team_count == 0 {
+            return Err(ScreenWarErrors::NotTeamChallenge.into());
+        }
+
+        User::validate_pda(user_pda, caller.key())?;
+
+        Team::validate_pda(team_pda, challenge.challenge_id, challenge.winning_team)?;
+
+        Ok(())
+    }
+
+    pub fn validate_caller_is_winning_team_member(
+        challenge: &Challenge,
+        user_pda: &User,
+    ) -> ProgramResult {
+        if user_pda.challenge_id != challenge.challenge_id {
+            return Err(ScreenWarErrors::NotEnrolled.into());
+        }
+
+        if challenge.winning_team == 0 || user_pda.team_id.ne(&challenge.winning_team) {
+            return Err(ScreenWarErrors::NotWinner.into());
+        }
+
+        if user_pda.team_reward_claimed {
+            return Err(ScreenWarErrors::RewardAlreadyClaimed.into());
+        }
+
         Ok(())
     }
 
@@ -185,37 +288,32 @@ impl<'a> ClaimRewards<'a> {
         Ok(())
     }
 
-    pub fn validate_contention_period_is_over(end: i64) -> ProgramResult {
-        let now = Clock::get()?.unix_timestamp;
-        let five_days = 5 * 24 * 60 * 60;
-        let contention_period = end
-            .checked_add(five_days)
-            .ok_or(ScreenWarErrors::ContentionPhase)?;
-
-        if contention_period > now {
-            return Err(ScreenWarErrors::ContentionPhase.into());
+    // dev : settlement needs challenge.creator and both duelists' user_pdas intact to pay out stakes,
+    // so it must precede claims and withdrawals
+    pub fn validate_duel_is_settled(challenge: &Challenge) -> ProgramResult {
+        if challenge.is_duel && !challenge.duel_settled {
+            return Err(ScreenWarErrors::DuelNotSettled.into());
         }
 
         Ok(())
     }
 
+    // dev : global pda is owned by this program, so lamports are moved directly without a cpi
     pub fn transfer_rewards(
         global: &AccountInfo,
         user: &AccountInfo,
         rewards: u64,
-        global_bump: u8,
     ) -> ProgramResult {
         if rewards > 0 {
-            let global_bump_binding = [global_bump];
-            let seeds = &[Seed::from(b"global"), Seed::from(&global_bump_binding)];
-            let global_pda_signature = Signer::from(seeds);
-
-            Transfer {
-                from: global,
-                to: user,
-                lamports: rewards,
-            }
-            .invoke_signed(&[global_pda_signature])?;
+            let mut global_lamports = global.try_borrow_mut_lamports()?;
+            *global_lamports = global_lamports
+                .checked_sub(rewards)
+                .ok_or(ScreenWarErrors::IntegerUnderflow)?;
+
+            let mut user_lamports = user.try_borrow_mut_lamports()?;
+            *user_lamports = user_lamports
+                .checked_add(rewards)
+                .ok_or(ScreenWarErrors::IntegerOverflow)?;
         }
 
         Ok(())
@@ -279,11 +377,6 @@ impl<'a> ClaimRewards<'a> {
         ))
     }
 
-    // close_challenge_account -> to_do!()
-    pub fn close_challenge_account(challenge: &mut Challenge) -> ProgramResult {
-        todo!();
-    }
-
     // update treasury profits
     pub fn update_treasury_profits(global: &mut Global, amount: u64) -> ProgramResult {
         global.treasury_profits = global
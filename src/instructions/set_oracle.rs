@@ -0,0 +1,101 @@
+use {
+    crate::{
+        events::Event,
+        state::Global,
+        utils::{check_account, AccountCheck, Decoder},
+        ScreenWarErrors,
+    },
+    core::convert::TryFrom,
+    pinocchio::{
+        account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+    },
+};
+
+pub struct SetOracle<'a> {
+    pub accounts: SetOracleAccounts<'a>,
+    pub instruction_data: SetOracleInstructionData,
+}
+
+pub struct SetOracleAccounts<'a> {
+    pub admin: &'a AccountInfo,
+    pub global: &'a AccountInfo,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SetOracleInstructionData {
+    pub oracle: Pubkey, // dev : default pubkey disables batch syncing
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], SetOracleInstructionData)> for SetOracle<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo], SetOracleInstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = SetOracleAccounts::try_from(accounts)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetOracleAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [admin, global] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // dev : later admin key is validated against global.admin in validate_admin() function
+        check_account(admin, &[AccountCheck::Signer])?;
+        check_account(global, &[AccountCheck::Writable, AccountCheck::Owned])?;
+
+        Global::validate_pda(global)?;
+
+        Ok(Self { admin, global })
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetOracleInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
+        let mut data = Decoder::new(instruction_data);
+        let oracle = data.pubkey()?;
+        data.finish()?;
+
+        Ok(Self { oracle })
+    }
+}
+
+impl<'a> SetOracle<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &14;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // get mutable ref to Global Pda
+        let mut global = Global::from_account_info_mut(self.accounts.global)?;
+
+        // validate admin
+        Self::validate_admin(&mut global, self.accounts.admin)?;
+
+        global.oracle = self.instruction_data.oracle;
+
+        Event::OracleSet {
+            oracle: global.oracle,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    pub fn validate_admin(global: &mut Global, caller: &AccountInfo) -> ProgramResult {
+        if global.admin.ne(caller.key()) {
+            return Err(ScreenWarErrors::NotAdmin)?;
+        };
+
+        Ok(())
+    }
+}
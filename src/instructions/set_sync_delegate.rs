@@ -0,0 +1,143 @@
+use {
+    crate::{
+        events::Event,
+        state::User,
+        utils::{check_account, AccountCheck, Decoder},
+        ScreenWarErrors,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{
+            clock::{Clock, CLOCK_ID},
+            Sysvar,
+        },
+        ProgramResult,
+    },
+};
+
+pub struct SetSyncDelegate<'a> {
+    pub accounts: SetSyncDelegateAccounts<'a>,
+    pub instruction_data: SetSyncDelegateInstructionData,
+}
+
+pub struct SetSyncDelegateAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub user_pda: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SetSyncDelegateInstructionData {
+    pub delegate: Pubkey, // dev : default pubkey revokes the current delegate
+    pub delegate_expiry: i64,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], SetSyncDelegateInstructionData)> for SetSyncDelegate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo], SetSyncDelegateInstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = SetSyncDelegateAccounts::try_from(accounts)?;
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetSyncDelegateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, user_pda, clock_sysvar] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // dev : only the participant wallet itself can hand out or revoke a delegate
+        check_account(user, &[AccountCheck::Signer])?;
+        check_account(user_pda, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(clock_sysvar, &[AccountCheck::Address(&CLOCK_ID)])?;
+
+        User::validate_pda(user_pda, user.key())?;
+
+        Ok(Self {
+            user,
+            user_pda,
+            clock_sysvar,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetSyncDelegateInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
+        // dev
+        // first 32 bytes would be the delegate pubkey
+        // next 8 bytes would be the delegate expiry timestamp
+        let mut data = Decoder::new(instruction_data);
+        let delegate = data.pubkey()?;
+        let delegate_expiry = data.i64()?;
+        data.finish()?;
+
+        Ok(Self {
+            delegate,
+            delegate_expiry,
+        })
+    }
+}
+
+impl<'a> SetSyncDelegate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &13;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut user_pda = User::from_account_info_mut(self.accounts.user_pda)?;
+
+        // validations
+        let now = Clock::get()?.unix_timestamp;
+        Self::validate_delegate(
+            self.accounts.user,
+            &self.instruction_data.delegate,
+            self.instruction_data.delegate_expiry,
+            now,
+        )?;
+
+        user_pda.delegate = self.instruction_data.delegate;
+        user_pda.delegate_expiry = self.instruction_data.delegate_expiry;
+
+        Event::SyncDelegateSet {
+            user: *self.accounts.user.key(),
+            delegate: user_pda.delegate,
+            delegate_expiry: user_pda.delegate_expiry,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    pub fn validate_delegate(
+        user: &AccountInfo,
+        delegate: &Pubkey,
+        delegate_expiry: i64,
+        now: i64,
+    ) -> ProgramResult {
+        // dev : revoking needs no further checks
+        if delegate.eq(&Pubkey::default()) {
+            return Ok(());
+        }
+
+        if delegate.eq(user.key()) {
+            return Err(ScreenWarErrors::InvalidDelegate.into());
+        }
+
+        if delegate_expiry <= now {
+            return Err(ScreenWarErrors::DelegateExpired.into());
+        }
+
+        Ok(())
+    }
+}
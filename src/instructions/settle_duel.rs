@@ -0,0 +1,273 @@
+use {
+    crate::{
+        events::Event,
+        state::{Challenge, Lifecycle, User},
+        utils::{check_account, check_unique, AccountCheck, Decoder},
+        ScreenWarErrors,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::{find_program_address, Pubkey},
+        sysvars::{
+            clock::{Clock, CLOCK_ID},
+            Sysvar,
+        },
+        ProgramResult,
+    },
+};
+
+pub struct SettleDuel<'a> {
+    pub accounts: SettleDuelAccounts<'a>,
+    pub instruction_data: SettleDuelInstructionData,
+}
+
+pub struct SettleDuelAccounts<'a> {
+    pub caller: &'a AccountInfo,
+    pub challenge: &'a AccountInfo,
+    pub creator: &'a AccountInfo,
+    pub opponent: &'a AccountInfo,
+    pub creator_user_pda: &'a AccountInfo,
+    pub opponent_user_pda: &'a AccountInfo,
+    pub clock_sysvar: &'a AccountInfo,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SettleDuelInstructionData {
+    pub challenge_id: u32,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], SettleDuelInstructionData)> for SettleDuel<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo], SettleDuelInstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = SettleDuelAccounts::try_from(accounts)?;
+
+        // validate correct challenge pda
+        Challenge::validate_pda(accounts.challenge, instruction_data.challenge_id)?;
+
+        // return Self
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SettleDuelAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [caller, challenge, creator, opponent, creator_user_pda, opponent_user_pda, clock_sysvar] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // dev : settlement is permissionless, payouts can only reach the wallets stored in challenge
+        check_account(caller, &[AccountCheck::Signer])?;
+        check_account(challenge, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(creator, &[AccountCheck::Writable])?;
+        check_account(opponent, &[AccountCheck::Writable])?;
+        check_account(clock_sysvar, &[AccountCheck::Address(&CLOCK_ID)])?;
+        check_unique([creator, opponent])?;
+
+        SettleDuel::validate_duelist_pda(creator_user_pda, creator)?;
+        SettleDuel::validate_duelist_pda(opponent_user_pda, opponent)?;
+
+        Ok(Self {
+            caller,
+            challenge,
+            creator,
+            opponent,
+            creator_user_pda,
+            opponent_user_pda,
+            clock_sysvar,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for SettleDuelInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
+        let mut data = Decoder::new(instruction_data);
+        let challenge_id = data.u32()?;
+        data.finish()?;
+
+        Ok(Self { challenge_id })
+    }
+}
+
+impl<'a> SettleDuel<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &11;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // get the mutable reference to challenge pda
+        let mut challenge = Challenge::from_account_info_mut(self.accounts.challenge)?;
+
+        // validations
+        let now = Clock::get()?.unix_timestamp;
+        Self::validate_challenge_is_duel(challenge.is_duel)?;
+        challenge.lifecycle(now)?.validate(&[
+            Lifecycle::Contention,
+            Lifecycle::Settlement,
+            Lifecycle::Closed,
+        ])?;
+        Self::validate_duel_not_settled(challenge.duel_settled)?;
+        Self::validate_duelists(
+            &challenge,
+            self.accounts.creator.key(),
+            self.accounts.opponent.key(),
+        )?;
+
+        // dev : a duelist who never joined has no stake in escrow and no streak to compare
+        let creator_streak = Self::read_duelist_streak(
+            self.accounts.creator_user_pda,
+            challenge.challenge_id,
+            &challenge.creator,
+        )?;
+        let opponent_streak = Self::read_duelist_streak(
+            self.accounts.opponent_user_pda,
+            challenge.challenge_id,
+            &challenge.opponent,
+        )?;
+
+        let stake = challenge.duel_stake;
+        let (creator_payout, opponent_payout) = match (creator_streak, opponent_streak) {
+            (Some(creator_streak), Some(opponent_streak)) => {
+                let both_stakes = stake
+                    .checked_mul(2)
+                    .ok_or(ScreenWarErrors::IntegerOverflow)?;
+
+                if creator_streak > opponent_streak {
+                    let creator = challenge.creator;
+                    Self::write_winner(&mut challenge, creator, creator_streak)?;
+                    (both_stakes, 0)
+                } else if opponent_streak > creator_streak {
+                    let opponent = challenge.opponent;
+                    Self::write_winner(&mut challenge, opponent, opponent_streak)?;
+                    (0, both_stakes)
+                } else {
+                    (stake, stake)
+                }
+            }
+            // dev : unanswered invitation, refund whoever staked
+            (Some(_), None) => (stake, 0),
+            (None, Some(_)) => (0, stake),
+            (None, None) => (0, 0),
+        };
+
+        challenge.duel_settled = true;
+        drop(challenge);
+
+        // payout escrowed stakes
+        Self::release_stake(
+            self.accounts.challenge,
+            self.accounts.creator,
+            creator_payout,
+        )?;
+        Self::release_stake(
+            self.accounts.challenge,
+            self.accounts.opponent,
+            opponent_payout,
+        )?;
+
+        Event::DuelSettled {
+            challenge_id: self.instruction_data.challenge_id,
+            creator_payout,
+            opponent_payout,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    pub fn validate_challenge_is_duel(is_duel: bool) -> ProgramResult {
+        if !is_duel {
+            return Err(ScreenWarErrors::NotDuelChallenge.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_duel_not_settled(duel_settled: bool) -> ProgramResult {
+        if duel_settled {
+            return Err(ScreenWarErrors::DuelAlreadySettled.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_duelists(
+        challenge: &Challenge,
+        creator: &Pubkey,
+        opponent: &Pubkey,
+    ) -> ProgramResult {
+        if challenge.creator.ne(creator) || challenge.opponent.ne(opponent) {
+            return Err(ScreenWarErrors::NotDuelist.into());
+        }
+
+        Ok(())
+    }
+
+    // dev : a duelist who never joined has no user_pda yet, so there is no stored bump to reuse
+    pub fn validate_duelist_pda(user_pda: &AccountInfo, duelist: &AccountInfo) -> ProgramResult {
+        if user_pda.is_owned_by(&crate::ID) {
+            User::validate_pda(user_pda, duelist.key())?;
+            return Ok(());
+        }
+
+        let (user_pda_key, _) =
+            find_program_address(&[b"user", duelist.key().as_slice()], &crate::ID);
+        if user_pda_key.ne(user_pda.key()) {
+            return Err(ScreenWarErrors::InvalidUserPDA.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn read_duelist_streak(
+        user_pda: &AccountInfo,
+        challenge_id: u32,
+        duelist: &Pubkey,
+    ) -> Result<Option<u8>, ProgramError> {
+        if !user_pda.is_owned_by(&crate::ID) {
+            return Ok(None);
+        }
+
+        let user_pda = User::from_account_info(user_pda)?;
+
+        if user_pda.challenge_id != challenge_id || user_pda.user.ne(duelist) {
+            return Ok(None);
+        }
+
+        Ok(Some(user_pda.streak))
+    }
+
+    pub fn write_winner(challenge: &mut Challenge, winner: Pubkey, streak: u8) -> ProgramResult {
+        challenge.winner = winner;
+        challenge.winner_streak = streak;
+
+        Ok(())
+    }
+
+    // dev : challenge pda is owned by this program, so lamports are moved directly without a cpi
+    pub fn release_stake(challenge: &AccountInfo, to: &AccountInfo, amount: u64) -> ProgramResult {
+        if amount > 0 {
+            let mut challenge_lamports = challenge.try_borrow_mut_lamports()?;
+            *challenge_lamports = challenge_lamports
+                .checked_sub(amount)
+                .ok_or(ScreenWarErrors::IntegerUnderflow)?;
+
+            let mut to_lamports = to.try_borrow_mut_lamports()?;
+            *to_lamports = to_lamports
+                .checked_add(amount)
+                .ok_or(ScreenWarErrors::IntegerOverflow)?;
+        }
+
+        Ok(())
+    }
+}
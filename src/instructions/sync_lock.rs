@@ -1,15 +1,20 @@
 use {
     crate::{
-        mock_oracle::{mock_offchain_oracle_component, DebugData},
-        state::{Challenge, User},
+        attestation::SyncAttestation,
+        events::Event,
+        state::{validate_owner, Challenge, CustomPenalty, Excusal, Global, Lifecycle, Team, User},
+        utils::{check_account, check_system_program, AccountCheck, Decoder},
         ScreenWarErrors,
     },
     pinocchio::{
         account_info::AccountInfo,
-        instruction::{Seed, Signer},
-        program_error::{self, ProgramError},
-        pubkey::{find_program_address, Pubkey},
-        sysvars::{clock::Clock, Sysvar},
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{
+            clock::{Clock, CLOCK_ID},
+            instructions::INSTRUCTIONS_ID,
+            Sysvar,
+        },
         ProgramResult,
     },
     pinocchio_system::instructions::Transfer,
@@ -27,32 +32,39 @@ pub struct SyncLockAccounts<'a> {
     pub user_pda: &'a AccountInfo,
     pub clock_sysvar: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
+    pub instructions_sysvar: &'a AccountInfo, // dev : holds the oracle attestation, see SyncAttestation::verify()
+    // dev : optional accounts in fixed order, each present only when the sync requires it
+    // [team_pda (team challenge), delegate (session key signing instead of user), excusal pdas (up to Excusal::MAX_SCOPES)]
+    // an excusal left out only costs the participant the days it would have excused
+    pub remaining_accounts: &'a [AccountInfo],
     pub global_bump: u8,
 }
 
+#[derive(Debug, PartialEq)]
 pub struct SyncLockInstructionData {
     pub challenge_id: u32,
     pub debug_data: Option<DebugData>, // dev-practice : later try with scenarios where there is another Type after option, see how deserialization of instrucitions become different
 }
 
-impl<'a> TryFrom<(&'a [AccountInfo], &'a [u8])> for SyncLock<'a> {
+// dev : scripted oracle result, honoured by mock-oracle builds only, see sync_result()
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+pub struct DebugData {
+    pub user_passed: bool,
+    pub days_not_synced: u8,
+    pub synced_today: bool,
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], SyncLockInstructionData)> for SyncLock<'a> {
     type Error = ProgramError;
 
     fn try_from(
-        (accounts, instruction_data): (&'a [AccountInfo], &'a [u8]),
+        (accounts, instruction_data): (&'a [AccountInfo], SyncLockInstructionData),
     ) -> Result<Self, Self::Error> {
         let accounts = SyncLockAccounts::try_from(accounts)?;
-        let instruction_data = SyncLockInstructionData::try_from(instruction_data)?;
 
         // validate correct challenge pda
-        let (challenge_pda_key, _) = find_program_address(
-            &[b"challenge", &instruction_data.challenge_id.to_le_bytes()],
-            &crate::ID,
-        );
-
-        if challenge_pda_key.ne(accounts.challenge.key()) {
-            return Err(ScreenWarErrors::InvalidChallengePDA.into());
-        }
+        Challenge::validate_pda(accounts.challenge, instruction_data.challenge_id)?;
 
         // return Self
         Ok(Self {
@@ -66,24 +78,27 @@ impl<'a> TryFrom<&'a [AccountInfo]> for SyncLockAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [user, global, challenge, user_pda, clock_sysvar, system_program] = accounts else {
+        let [user, global, challenge, user_pda, clock_sysvar, system_program, instructions_sysvar, remaining @ ..] =
+            accounts
+        else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        if !user.is_signer() {
-            return Err(ScreenWarErrors::NotSigner.into());
-        }
-
-        let (user_pda_key, _) = find_program_address(&[b"user", user.key().as_slice()], &crate::ID);
+        // dev : when user is not a signer, the delegate stored in user_pda must sign, see validate_delegate()
+        check_account(user, &[AccountCheck::Writable])?;
+        check_account(global, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(challenge, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(user_pda, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(clock_sysvar, &[AccountCheck::Address(&CLOCK_ID)])?;
+        check_system_program(system_program)?;
+        check_account(
+            instructions_sysvar,
+            &[AccountCheck::Address(&INSTRUCTIONS_ID)],
+        )?;
 
-        if (&user_pda_key).ne(user_pda.key()) {
-            return Err(ScreenWarErrors::InvalidUserPDA.into());
-        };
+        User::validate_pda(user_pda, user.key())?;
 
-        let (global_pda_key, global_bump) = find_program_address(&[b"global"], &crate::ID);
-        if global.key().ne(&global_pda_key) {
-            return Err(ProgramError::InvalidSeeds);
-        };
+        let global_bump = Global::validate_pda(global)?;
 
         Ok(Self {
             user,
@@ -92,6 +107,8 @@ impl<'a> TryFrom<&'a [AccountInfo]> for SyncLockAccounts<'a> {
             user_pda,
             clock_sysvar,
             system_program,
+            instructions_sysvar,
+            remaining_accounts: remaining,
             global_bump,
         })
     }
@@ -101,36 +118,27 @@ impl<'a> TryFrom<&'a [u8]> for SyncLockInstructionData {
     type Error = ProgramError;
 
     fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
-        // dev : mental_model
-        // challenge_id = 8*4 = 4 bytes
-        // Option (None/Some) = 1 byte
-        // DebugData
-        //   .. bool = 1 byte
-        //   ... u8 = 1 byte
-        //   ... bool = 1 byte
-        // 8 bytes array
-        // challenge_id :  bytes[0..=3]  [0,1,2,3]
-        // Option<None/Some> : bytes[4]  [4]
-        // DebugData :  bytes[5..=7]     [5,6,7] : [user_passed, days_not_synced, synced_today]
-        //
-
-        // Option<DebugData>
-        //         pub struct DebugData {
-        //     pub user_passed: bool,
-        //     pub days_not_synced: u8,
-        //     pub synced_today: bool,
-        // }
-
-        let challenge_id = u32::from_le_bytes(instruction_data[0..=3].try_into().unwrap());
-        let debug_data: Option<DebugData> = match instruction_data[4] {
-            0 => None,
+        // dev
+        // first 4 bytes would be challenge_id
+        // next 4 bytes would be Option<DebugData> : [tag, user_passed, days_not_synced, synced_today]
+        // dev : None keeps the fixed width, its 3 value bytes must be zero
+        let mut data = Decoder::new(instruction_data);
+        let challenge_id = data.u32()?;
+        let debug_data = match data.u8()? {
+            0 => {
+                if data.array::<3>()? != [0; 3] {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+                None
+            }
             1 => Some(DebugData {
-                user_passed: instruction_data[5] != 0,
-                days_not_synced: instruction_data[6],
-                synced_today: instruction_data[7] != 0,
+                user_passed: data.bool()?,
+                days_not_synced: data.u8()?,
+                synced_today: data.bool()?,
             }),
-            _ => None,
+            _ => return Err(ProgramError::InvalidInstructionData),
         };
+        data.finish()?;
 
         Ok(Self {
             challenge_id,
@@ -142,34 +150,174 @@ impl<'a> TryFrom<&'a [u8]> for SyncLockInstructionData {
 impl<'a> SyncLock<'a> {
     pub const DISCRIMINATOR: &'a u8 = &9;
     pub const DAILY_LAMPORTS: u64 = 10_000_000;
+    pub const DEFAULT_RETAINED_PER_DAY: u32 = 750_000;
 
     pub fn process(&mut self) -> ProgramResult {
+        let retained_per_day = Self::read_retained_per_day(self.accounts.challenge)?;
+
         // get mutable refs
-        let mut challenge_raw_data = self.accounts.challenge.try_borrow_mut_data()?;
-        let mut challenge = Challenge::load_mut(&mut challenge_raw_data)?;
+        let mut challenge = Challenge::from_account_info_mut(self.accounts.challenge)?;
 
-        let mut user_pda_raw_data = self.accounts.user_pda.try_borrow_mut_data()?;
-        let user_pda = User::load_mut(&mut user_pda_raw_data)?;
+        let mut user_pda = User::from_account_info_mut(self.accounts.user_pda)?;
 
         // validations
         let now = Clock::get()?.unix_timestamp;
-        Self::validate_challenge_has_started(now, challenge.start)?;
-        Self::validate_challenge_has_not_ended(now, challenge.end)?;
+        challenge.lifecycle(now)?.validate(&[Lifecycle::Active])?;
 
+        let mut remaining_accounts = self.accounts.remaining_accounts.iter();
+        let team_pda = if user_pda.team_id > 0 {
+            remaining_accounts.next()
+        } else {
+            None
+        };
+
+        // dev : session keys can only sync, and only against the prepaid balance
+        let delegated = !self.accounts.user.is_signer();
+        if delegated {
+            Self::validate_delegate(remaining_accounts.next(), &user_pda, now)?;
+        }
+
+        let oracle = Global::from_account_info(self.accounts.global)?.oracle;
+        let today = challenge.day(now)?;
         let (user_passed_today, days_not_synced, synced_today) =
-            mock_offchain_oracle_component(&self.instruction_data.debug_data)?;
+            self.sync_result(&oracle, &user_pda, today)?;
 
         Self::validated_today_not_synced_already(synced_today)?;
 
-        let today = 1;
+        // excused days are neither deposited for nor judged
+        let (days_excused, today_excused) = Self::excused_days(
+            remaining_accounts,
+            &challenge,
+            user_pda.next_sync_day,
+            days_not_synced,
+        )?;
+        let days_not_synced = days_not_synced - days_excused;
+        let settled_today = (!today_excused).then_some(user_passed_today);
+
+        let streak_before_sync = user_pda.streak;
+
+        let today = u8::from(!today_excused);
 
         // deposit
-        Self::deposit_total_daily_lamports(self, days_not_synced + today)?;
+        Self::deposit_total_daily_lamports(
+            self,
+            &mut user_pda,
+            days_not_synced
+                .checked_add(today)
+                .ok_or(ScreenWarErrors::IntegerOverflow)?,
+            delegated,
+        )?;
+
+        let slashed = Self::apply_sync_result(
+            &mut challenge,
+            &mut user_pda,
+            settled_today,
+            days_not_synced,
+            days_excused,
+            retained_per_day,
+        )?;
+
+        // team aggregate follows every member's streak change
+        if user_pda.team_id > 0 {
+            Self::update_team_streak(
+                team_pda,
+                challenge.challenge_id,
+                user_pda.team_id,
+                streak_before_sync,
+                user_pda.streak,
+            )?;
+        }
 
+        Event::Synced {
+            challenge_id: challenge.challenge_id,
+            user: user_pda.user,
+            passed: settled_today,
+            days_not_synced,
+            streak: user_pda.streak,
+            slashed,
+            locked_balance: user_pda.locked_balance,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    // dev : (user_passed_today, days_not_synced, synced_today) as attested by the oracle
+    // an attestation is only accepted for the next unsynced day, so synced_today is always false
+    pub fn sync_result(
+        &self,
+        oracle: &Pubkey,
+        user_pda: &User,
+        today: u32,
+    ) -> Result<(bool, u8, bool), ProgramError> {
+        match &self.instruction_data.debug_data {
+            #[cfg(feature = "mock-oracle")]
+            Some(debug) => crate::mock_oracle::mock_offchain_oracle_component(debug),
+
+            #[cfg(not(feature = "mock-oracle"))]
+            Some(_) => Err(ScreenWarErrors::MockOracleDisabled.into()),
+
+            None => {
+                let attestation = SyncAttestation::verify(
+                    self.accounts.instructions_sysvar,
+                    oracle,
+                    &user_pda.device,
+                )?;
+                attestation.validate_sync(user_pda, self.instruction_data.challenge_id, today)?;
+
+                Ok((attestation.user_passed, attestation.days_not_synced, false))
+            }
+        }
+    }
+
+    // dev : (missed days excused, today excused) over the days this sync settles, next_sync_day..=today
+    pub fn excused_days<'b>(
+        excusals: impl Iterator<Item = &'b AccountInfo>,
+        challenge: &Challenge,
+        next_sync_day: u32,
+        days_not_synced: u8,
+    ) -> Result<(u8, bool), ProgramError> {
+        let mut scopes = [None; Excusal::MAX_SCOPES];
+        for (scope, excusal) in scopes.iter_mut().zip(excusals) {
+            *scope = Some(Excusal::for_challenge(excusal, challenge.challenge_id)?);
+        }
+
+        let is_excused = |day: u32| -> Result<bool, ProgramError> {
+            let (start, end) = challenge.day_window(day)?;
+            Ok(scopes
+                .iter()
+                .flatten()
+                .any(|scope| scope.excuses(start, end)))
+        };
+
+        let mut days_excused = 0;
+        for missed in 0..days_not_synced {
+            if is_excused(next_sync_day + missed as u32)? {
+                days_excused += 1;
+            }
+        }
+        let today_excused = is_excused(next_sync_day + days_not_synced as u32)?;
+
+        Ok((days_excused, today_excused))
+    }
+
+    // dev : streak, slash and total_slashed bookkeeping shared by SyncLock and BatchSync, returns total penalty
+    // user_passed_today is None when today is excused, days_excused are skipped without touching the streak
+    pub fn apply_sync_result(
+        challenge: &mut Challenge,
+        user_pda: &mut User,
+        user_passed_today: Option<bool>,
+        days_not_synced: u8,
+        days_excused: u8,
+        retained_per_day: u32,
+    ) -> Result<u64, ProgramError> {
         let mut days_not_synced_or_failed = days_not_synced;
+        let mut total_penalty = 0;
 
-        if !user_passed_today {
-            days_not_synced_or_failed += 1;
+        if user_passed_today == Some(false) {
+            days_not_synced_or_failed = days_not_synced_or_failed
+                .checked_add(1)
+                .ok_or(ScreenWarErrors::IntegerOverflow)?;
         }
 
         if days_not_synced_or_failed > 0 {
@@ -179,6 +327,7 @@ impl<'a> SyncLock<'a> {
             let lb_penalty = Self::calculate_exponential_penalty_on_locked_balance(
                 current_balance,
                 days_not_synced_or_failed,
+                retained_per_day,
             )?;
 
             // slash
@@ -186,7 +335,7 @@ impl<'a> SyncLock<'a> {
 
             // dev : total penalty is applied by slashing all the  daily_lamports + 25% of previous locked_balance
             // :: (SyncLock::DAILY_LAMPORTS * days_not_synced_or_failed) + lb_penalty
-            let total_penalty = SyncLock::DAILY_LAMPORTS
+            total_penalty = SyncLock::DAILY_LAMPORTS
                 .checked_mul(days_not_synced_or_failed as u64)
                 .ok_or(ScreenWarErrors::IntegerOverflow)?
                 .checked_add(lb_penalty)
@@ -196,26 +345,72 @@ impl<'a> SyncLock<'a> {
             Self::update_total_slashed_in_challenge(challenge, total_penalty)?;
         }
 
-        if user_passed_today {
+        if user_passed_today == Some(true) {
             Self::increment_streak(user_pda)?;
             Self::update_users_locked_balance(user_pda, SyncLock::DAILY_LAMPORTS as i64)?;
             // increase
         }
 
-        Ok(())
+        // missed, excused days and today are settled, the next attestation starts after them
+        user_pda.next_sync_day = user_pda
+            .next_sync_day
+            .checked_add(days_not_synced as u32 + days_excused as u32 + 1)
+            .ok_or(ScreenWarErrors::IntegerOverflow)?;
+
+        Ok(total_penalty)
     }
 
-    pub fn validate_challenge_has_started(now: i64, challenge_start: i64) -> ProgramResult {
-        if now < challenge_start {
-            return Err(ScreenWarErrors::ChallengeNotStarted.into());
-        }
+    // dev : challenges without a CustomPenalty extension keep 75% of the locked balance per missed day
+    pub fn read_retained_per_day(challenge: &AccountInfo) -> Result<u32, ProgramError> {
+        validate_owner(challenge)?;
+        let rule = Challenge::extension::<CustomPenalty>(&challenge.try_borrow_data()?)?;
+
+        Ok(rule.map_or(Self::DEFAULT_RETAINED_PER_DAY, |rule| rule.retained_per_day))
+    }
+
+    pub fn update_team_streak(
+        team_pda: Option<&AccountInfo>,
+        challenge_id: u32,
+        team_id: u8,
+        streak_before: u8,
+        streak_after: u8,
+    ) -> ProgramResult {
+        let team_pda = team_pda.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        Team::validate_pda(team_pda, challenge_id, team_id)?;
+
+        let mut team = Team::from_account_info_mut(team_pda)?;
+
+        // dev : streak_sum - streak_before + streak_after, a reset removes the whole old streak
+        team.streak_sum = team
+            .streak_sum
+            .checked_sub(streak_before as u64)
+            .ok_or(ScreenWarErrors::IntegerUnderflow)?
+            .checked_add(streak_after as u64)
+            .ok_or(ScreenWarErrors::IntegerOverflow)?;
+
         Ok(())
     }
 
-    pub fn validate_challenge_has_not_ended(now: i64, challenge_end: i64) -> ProgramResult {
-        if now > challenge_end {
-            return Err(ScreenWarErrors::ChallengeEnded.into());
+    pub fn validate_delegate(
+        delegate: Option<&AccountInfo>,
+        user_pda: &User,
+        now: i64,
+    ) -> ProgramResult {
+        let delegate = delegate.ok_or(ScreenWarErrors::NotSigner)?;
+
+        if !delegate.is_signer() {
+            return Err(ScreenWarErrors::NotSigner.into());
+        }
+
+        if user_pda.delegate.eq(&Pubkey::default()) || delegate.key().ne(&user_pda.delegate) {
+            return Err(ScreenWarErrors::InvalidDelegate.into());
         }
+
+        if now > user_pda.delegate_expiry {
+            return Err(ScreenWarErrors::DelegateExpired.into());
+        }
+
         Ok(())
     }
 
@@ -226,17 +421,60 @@ impl<'a> SyncLock<'a> {
         Ok(())
     }
 
-    pub fn deposit_total_daily_lamports(&mut self, days_to_update: u8) -> ProgramResult {
+    pub fn deposit_total_daily_lamports(
+        &mut self,
+        user_pda: &mut User,
+        days_to_update: u8,
+        delegated: bool,
+    ) -> ProgramResult {
         let lamports = (days_to_update as u64)
             .checked_mul(Self::DAILY_LAMPORTS)
             .ok_or(ScreenWarErrors::IntegerOverflow)?;
 
-        Transfer {
-            from: self.accounts.user,
-            to: self.accounts.global,
-            lamports: lamports,
+        // dev : prepaid stake is drawn first, wallet only covers what prepaid balance can't
+        let from_prepaid = lamports.min(user_pda.prepaid_balance);
+        let from_wallet = lamports - from_prepaid;
+
+        if from_prepaid > 0 {
+            user_pda.prepaid_balance -= from_prepaid;
+            Self::draw_prepaid_lamports(
+                self.accounts.user_pda,
+                self.accounts.global,
+                from_prepaid,
+            )?;
+        }
+
+        if from_wallet > 0 && delegated {
+            return Err(ScreenWarErrors::InsufficientPrepaidBalance.into());
+        }
+
+        if from_wallet > 0 {
+            Transfer {
+                from: self.accounts.user,
+                to: self.accounts.global,
+                lamports: from_wallet,
+            }
+            .invoke()?;
         }
-        .invoke()?;
+
+        Ok(())
+    }
+
+    // dev : user_pda is owned by this program, so prepaid lamports are moved directly without a cpi
+    pub fn draw_prepaid_lamports(
+        user_pda: &AccountInfo,
+        global: &AccountInfo,
+        amount: u64,
+    ) -> ProgramResult {
+        let mut user_pda_lamports = user_pda.try_borrow_mut_lamports()?;
+        *user_pda_lamports = user_pda_lamports
+            .checked_sub(amount)
+            .ok_or(ScreenWarErrors::IntegerUnderflow)?;
+
+        let mut global_lamports = global.try_borrow_mut_lamports()?;
+        *global_lamports = global_lamports
+            .checked_add(amount)
+            .ok_or(ScreenWarErrors::IntegerOverflow)?;
 
         Ok(())
     }
@@ -259,17 +497,18 @@ impl<'a> SyncLock<'a> {
     pub fn calculate_exponential_penalty_on_locked_balance(
         current_balance: u64,
         days_not_synced_or_failed: u8,
+        retained_per_day: u32,
     ) -> Result<u64, ProgramError> {
         if current_balance == 0 {
             Ok(0)
         } else {
-            const SCALE: u128 = 1_000_000;
-            const RATE_75_PERCENT: u128 = 750_000;
+            const SCALE: u128 = CustomPenalty::PENALTY_SCALE as u128;
+            let rate = retained_per_day as u128;
 
             // Apply compounding: (RATE^days) / (SCALE^days)
 
-            // RATE_75_PERCENT ^ days_not_synced_or_failed
-            let numerator = RATE_75_PERCENT
+            // rate ^ days_not_synced_or_failed
+            let numerator = rate
                 .checked_pow(days_not_synced_or_failed as u32)
                 .ok_or(ScreenWarErrors::IntegerOverflow)?;
 
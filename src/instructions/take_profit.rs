@@ -1,13 +1,11 @@
 use {
-    crate::{state::Global, ScreenWarErrors},
-    pinocchio::{
-        account_info::AccountInfo,
-        instruction::{Seed, Signer},
-        program_error::ProgramError,
-        pubkey::find_program_address,
-        ProgramResult,
+    crate::{
+        events::Event,
+        state::Global,
+        utils::{check_account, check_system_program, AccountCheck, Decoder},
+        ScreenWarErrors,
     },
-    pinocchio_system::instructions::Transfer,
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult},
 };
 
 pub struct TakeProfit<'a> {
@@ -22,18 +20,18 @@ pub struct TakeProfitAccounts<'a> {
     pub global_bump: u8,
 }
 
+#[derive(Debug, PartialEq)]
 pub struct TakeProfitInstructionData {
     pub amount: u64,
 }
 
-impl<'a> TryFrom<(&'a [AccountInfo], &'a [u8])> for TakeProfit<'a> {
+impl<'a> TryFrom<(&'a [AccountInfo], TakeProfitInstructionData)> for TakeProfit<'a> {
     type Error = ProgramError;
 
     fn try_from(
-        (accounts, instruction_data): (&'a [AccountInfo], &'a [u8]),
+        (accounts, instruction_data): (&'a [AccountInfo], TakeProfitInstructionData),
     ) -> Result<Self, Self::Error> {
         let accounts = TakeProfitAccounts::try_from(accounts)?;
-        let instruction_data = TakeProfitInstructionData::try_from(instruction_data)?;
 
         Ok(Self {
             accounts,
@@ -51,14 +49,11 @@ impl<'a> TryFrom<&'a [AccountInfo]> for TakeProfitAccounts<'a> {
         };
 
         // dev : later admin key is validated against global.admin in validate_admin() function
-        if !admin.is_signer() {
-            return Err(ScreenWarErrors::NotSigner)?;
-        }
+        check_account(admin, &[AccountCheck::Signer, AccountCheck::Writable])?;
+        check_account(global, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_system_program(system_program)?;
 
-        let (global_pda_key, global_bump) = find_program_address(&[b"global"], &crate::ID);
-        if global.key().ne(&global_pda_key) {
-            return Err(ProgramError::InvalidSeeds);
-        };
+        let global_bump = Global::validate_pda(global)?;
 
         Ok(Self {
             admin,
@@ -73,11 +68,9 @@ impl<'a> TryFrom<&'a [u8]> for TakeProfitInstructionData {
     type Error = ProgramError;
 
     fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
-        if instruction_data.len().ne(&8usize) {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        let amount = u64::from_le_bytes(instruction_data.try_into().unwrap());
+        let mut data = Decoder::new(instruction_data);
+        let amount = data.u64()?;
+        data.finish()?;
 
         Ok(Self { amount })
     }
@@ -88,11 +81,10 @@ impl<'a> TakeProfit<'a> {
 
     pub fn process(&mut self) -> ProgramResult {
         // get mutable ref to Global Pda
-        let mut global_raw_data = self.accounts.global.try_borrow_mut_data()?;
-        let global = Global::load_mut(&mut global_raw_data)?;
+        let mut global = Global::from_account_info_mut(self.accounts.global)?;
 
         // validate admin
-        Self::validate_admin(global, self.accounts.admin)?;
+        Self::validate_admin(&mut global, self.accounts.admin)?;
 
         // validate protocol is solvent to payoff all user funds + rewards
         Self::validate_solvency(global.treasury_profits, self.instruction_data.amount)?;
@@ -102,11 +94,16 @@ impl<'a> TakeProfit<'a> {
             self.accounts.global,
             self.accounts.admin,
             self.instruction_data.amount,
-            self.accounts.global_bump,
         )?;
 
         // decrease global profits
-        Self::update_treasury_profits(global, self.instruction_data.amount)?;
+        Self::update_treasury_profits(&mut global, self.instruction_data.amount)?;
+
+        Event::ProfitTaken {
+            admin: *self.accounts.admin.key(),
+            amount: self.instruction_data.amount,
+        }
+        .emit();
 
         Ok(())
     }
@@ -126,23 +123,22 @@ impl<'a> TakeProfit<'a> {
         Ok(())
     }
 
+    // dev : global pda is owned by this program, so lamports are moved directly without a cpi
     pub fn withdraw_from_treasury(
         global: &AccountInfo,
         admin: &AccountInfo,
         amount: u64,
-        global_bump: u8,
     ) -> ProgramResult {
         if amount > 0 {
-            let global_bump_binding = [global_bump];
-            let seeds = &[Seed::from(b"global"), Seed::from(&global_bump_binding)];
-            let global_pda_signature = Signer::from(seeds);
-
-            Transfer {
-                from: global,
-                to: admin,
-                lamports: amount,
-            }
-            .invoke_signed(&[global_pda_signature])?;
+            let mut global_lamports = global.try_borrow_mut_lamports()?;
+            *global_lamports = global_lamports
+                .checked_sub(amount)
+                .ok_or(ScreenWarErrors::IntegerUnderflow)?;
+
+            let mut admin_lamports = admin.try_borrow_mut_lamports()?;
+            *admin_lamports = admin_lamports
+                .checked_add(amount)
+                .ok_or(ScreenWarErrors::IntegerOverflow)?;
         }
 
         Ok(())
@@ -1,10 +1,12 @@
 use {
-    crate::{state::Global, ScreenWarErrors},
-    core::convert::TryFrom,
-    pinocchio::{
-        account_info::AccountInfo, program_error::ProgramError, pubkey::find_program_address,
-        ProgramResult,
+    crate::{
+        events::Event,
+        state::Global,
+        utils::{check_account, AccountCheck, Decoder},
+        ScreenWarErrors,
     },
+    core::convert::TryFrom,
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult},
 };
 
 pub struct ToggleChallengeCreation<'a> {
@@ -17,18 +19,20 @@ pub struct ToggleChallengeCreationAccounts<'a> {
     pub global: &'a AccountInfo,
 }
 
+#[derive(Debug, PartialEq)]
 pub struct ToggleChallengeCreationInstructionData {
     pub pause: bool,
 }
 
-impl<'a> TryFrom<(&'a [AccountInfo], &'a [u8])> for ToggleChallengeCreation<'a> {
+impl<'a> TryFrom<(&'a [AccountInfo], ToggleChallengeCreationInstructionData)>
+    for ToggleChallengeCreation<'a>
+{
     type Error = ProgramError;
 
     fn try_from(
-        (accounts, instruction_data): (&'a [AccountInfo], &'a [u8]),
+        (accounts, instruction_data): (&'a [AccountInfo], ToggleChallengeCreationInstructionData),
     ) -> Result<Self, Self::Error> {
         let accounts = ToggleChallengeCreationAccounts::try_from(accounts)?;
-        let instruction_data = ToggleChallengeCreationInstructionData::try_from(instruction_data)?;
 
         Ok(Self {
             accounts,
@@ -46,14 +50,10 @@ impl<'a> TryFrom<&'a [AccountInfo]> for ToggleChallengeCreationAccounts<'a> {
         };
 
         // dev : later admin key is validated against global.admin in validate_admin() function
-        if !admin.is_signer() {
-            return Err(ScreenWarErrors::NotSigner)?;
-        }
+        check_account(admin, &[AccountCheck::Signer])?;
+        check_account(global, &[AccountCheck::Writable, AccountCheck::Owned])?;
 
-        let (global_pda_key, _) = find_program_address(&[b"global"], &crate::ID);
-        if global.key().ne(&global_pda_key) {
-            return Err(ProgramError::InvalidSeeds);
-        };
+        Global::validate_pda(global)?;
 
         Ok(Self { admin, global })
     }
@@ -63,15 +63,9 @@ impl<'a> TryFrom<&'a [u8]> for ToggleChallengeCreationInstructionData {
     type Error = ProgramError;
 
     fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
-        if instruction_data.len().ne(&1usize) {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        let pause = match instruction_data[0] {
-            0 => false,
-            1 => true,
-            _ => return Err(ProgramError::InvalidInstructionData),
-        };
+        let mut data = Decoder::new(instruction_data);
+        let pause = data.bool()?;
+        data.finish()?;
 
         Ok(Self { pause })
     }
@@ -82,11 +76,10 @@ impl<'a> ToggleChallengeCreation<'a> {
 
     pub fn process(&mut self) -> ProgramResult {
         // get mutable ref to Global Pda
-        let mut global_raw_data = self.accounts.global.try_borrow_mut_data()?;
-        let global = Global::load_mut(&mut global_raw_data)?;
+        let mut global = Global::from_account_info_mut(self.accounts.global)?;
 
         // validate admin
-        Self::validate_admin(global, self.accounts.admin)?;
+        Self::validate_admin(&mut global, self.accounts.admin)?;
 
         // validate toggle
         if global
@@ -99,6 +92,11 @@ impl<'a> ToggleChallengeCreation<'a> {
         // toggle pause state
         global.challenge_creation_paused = self.instruction_data.pause;
 
+        Event::ChallengeCreationToggled {
+            paused: self.instruction_data.pause,
+        }
+        .emit();
+
         Ok(())
     }
 
@@ -0,0 +1,127 @@
+use {
+    crate::{
+        events::Event,
+        state::{Challenge, Lifecycle},
+        utils::{check_account, AccountCheck, Decoder},
+        ScreenWarErrors,
+    },
+    pinocchio::{
+        account_info::AccountInfo,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+};
+
+pub struct UpdateAllowlist<'a> {
+    pub accounts: UpdateAllowlistAccounts<'a>,
+    pub instruction_data: UpdateAllowlistInstructionData,
+}
+
+pub struct UpdateAllowlistAccounts<'a> {
+    pub creator: &'a AccountInfo,
+    pub challenge: &'a AccountInfo,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UpdateAllowlistInstructionData {
+    pub challenge_id: u32,
+    pub allowlist_root: [u8; 32],
+}
+
+impl<'a> TryFrom<(&'a [AccountInfo], UpdateAllowlistInstructionData)> for UpdateAllowlist<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo], UpdateAllowlistInstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = UpdateAllowlistAccounts::try_from(accounts)?;
+
+        // validate correct challenge pda
+        Challenge::validate_pda(accounts.challenge, instruction_data.challenge_id)?;
+
+        // return Self
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateAllowlistAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [creator, challenge] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // dev : later creator key is validated against challenge.creator in validate_creator() function
+        check_account(creator, &[AccountCheck::Signer])?;
+        check_account(challenge, &[AccountCheck::Writable, AccountCheck::Owned])?;
+
+        Ok(Self { creator, challenge })
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for UpdateAllowlistInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
+        // dev
+        // first 4 bytes would be challenge_id
+        // next 32 bytes would be the new allowlist merkle root
+        let mut data = Decoder::new(instruction_data);
+        let challenge_id = data.u32()?;
+        let allowlist_root = data.array()?;
+        data.finish()?;
+
+        Ok(Self {
+            challenge_id,
+            allowlist_root,
+        })
+    }
+}
+
+impl<'a> UpdateAllowlist<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &10;
+
+    pub fn process(&mut self) -> ProgramResult {
+        // get mutable ref to challenge pda
+        let mut challenge = Challenge::from_account_info_mut(self.accounts.challenge)?;
+
+        // validations
+        let now = Clock::get()?.unix_timestamp;
+        Self::validate_creator(self.accounts.creator, challenge.creator)?;
+        Self::validate_challenge_is_private(challenge.is_private)?;
+        challenge.lifecycle(now)?.validate(&[Lifecycle::Pending])?;
+
+        // overwrite root, previously issued proofs stop verifying
+        challenge.allowlist_root = self.instruction_data.allowlist_root;
+
+        Event::AllowlistUpdated {
+            challenge_id: challenge.challenge_id,
+            allowlist_root: challenge.allowlist_root,
+        }
+        .emit();
+
+        Ok(())
+    }
+
+    pub fn validate_creator(caller: &AccountInfo, creator: Pubkey) -> ProgramResult {
+        if caller.key().ne(&creator) {
+            return Err(ScreenWarErrors::NotCreator.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_challenge_is_private(is_private: bool) -> ProgramResult {
+        if !is_private {
+            return Err(ScreenWarErrors::ChallengeNotPrivate.into());
+        }
+
+        Ok(())
+    }
+}
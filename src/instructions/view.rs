@@ -0,0 +1,250 @@
+use {
+    crate::{
+        instructions::{ClaimRewards, SyncLock, Withdraw},
+        state::{Challenge, User},
+        utils::{check_account, AccountCheck, Decoder},
+    },
+    core::mem::size_of,
+    pinocchio::{
+        account_info::AccountInfo,
+        cpi::set_return_data,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        sysvars::{clock::Clock, Sysvar},
+        ProgramResult,
+    },
+};
+
+// dev : views write nothing and need no signer, clients simulate them and read the return data
+pub struct View<'a> {
+    pub accounts: ViewAccounts<'a>,
+    pub instruction_data: ViewInstructionData,
+}
+
+pub struct ViewAccounts<'a> {
+    pub challenge: &'a AccountInfo,
+    // dev : [user_pda], required only for participant and penalty views
+    pub remaining_accounts: &'a [AccountInfo],
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ViewInstructionData {
+    pub challenge_id: u32,
+    pub days_missed: u8, // dev : penalty view only, 0 projects missing just today
+}
+
+// dev : return data layouts, packed like the account structs so clients decode them the same way
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct ParticipantView {
+    pub challenge_id: u32,
+    pub lifecycle: u8,
+    pub enrolled: bool,
+    pub streak: u8,
+    pub team_id: u8,
+    pub locked_balance: u64,
+    pub prepaid_balance: u64,
+    pub team_reward_claimed: bool,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct PenaltyView {
+    pub days_missed: u8,
+    pub retained_per_day: u32,
+    pub locked_balance: u64,
+    pub locked_balance_penalty: u64,
+    pub forfeited_deposits: u64,
+    pub total_penalty: u64,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct RewardsView {
+    pub lifecycle: u8,
+    pub total_slashed: u64,
+    pub winner_rewards: u64,
+    pub creator_rewards: u64,
+    pub treasury_profits: u64,
+    pub winner: Pubkey,
+    pub winning_team: u8,
+    pub winner_has_claimed: bool,
+    pub creator_has_claimed: bool,
+}
+
+pub trait ViewData: Copy + Default {
+    const LEN: usize = size_of::<Self>();
+
+    fn as_bytes(&self) -> &[u8] {
+        // dev : packed, no padding bytes to leak
+        unsafe { core::slice::from_raw_parts(self as *const Self as *const u8, Self::LEN) }
+    }
+
+    fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(unsafe { (bytes.as_ptr() as *const Self).read_unaligned() })
+    }
+}
+
+impl ViewData for ParticipantView {}
+impl ViewData for PenaltyView {}
+impl ViewData for RewardsView {}
+
+impl<'a> TryFrom<(&'a [AccountInfo], ViewInstructionData)> for View<'a> {
+    type Error = ProgramError;
+
+    fn try_from(
+        (accounts, instruction_data): (&'a [AccountInfo], ViewInstructionData),
+    ) -> Result<Self, Self::Error> {
+        let accounts = ViewAccounts::try_from(accounts)?;
+
+        // validate correct challenge pda
+        Challenge::validate_pda(accounts.challenge, instruction_data.challenge_id)?;
+
+        // return Self
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for ViewAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [challenge, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        check_account(challenge, &[AccountCheck::Owned])?;
+
+        Ok(Self {
+            challenge,
+            remaining_accounts: remaining,
+        })
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ViewInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
+        // dev
+        // first 4 bytes would be challenge_id
+        // optional last byte would be days_missed, penalty view only
+        let mut data = Decoder::new(instruction_data);
+        let challenge_id = data.u32()?;
+        let days_missed = if data.is_empty() { 0 } else { data.u8()? };
+        data.finish()?;
+
+        Ok(Self {
+            challenge_id,
+            days_missed,
+        })
+    }
+}
+
+impl<'a> View<'a> {
+    pub const PARTICIPANT_DISCRIMINATOR: &'a u8 = &17;
+    pub const PENALTY_DISCRIMINATOR: &'a u8 = &18;
+    pub const REWARDS_DISCRIMINATOR: &'a u8 = &19;
+
+    pub fn process_participant(&self) -> ProgramResult {
+        let challenge = Challenge::from_account_info(self.accounts.challenge)?;
+        let user_pda = self.user_pda()?;
+
+        let now = Clock::get()?.unix_timestamp;
+
+        let view = ParticipantView {
+            challenge_id: challenge.challenge_id,
+            lifecycle: challenge.lifecycle(now)? as u8,
+            enrolled: user_pda.challenge_id == challenge.challenge_id,
+            streak: user_pda.streak,
+            team_id: user_pda.team_id,
+            locked_balance: user_pda.locked_balance,
+            prepaid_balance: user_pda.prepaid_balance,
+            team_reward_claimed: user_pda.team_reward_claimed,
+        };
+
+        set_return_data(view.as_bytes());
+
+        Ok(())
+    }
+
+    // dev : what the next sync would slash if the participant misses days_missed days, same math as apply_sync_result()
+    // days_missed 0 is read as 1, missing just today, and the view echoes the count it used
+    pub fn process_penalty(&self) -> ProgramResult {
+        let retained_per_day = SyncLock::read_retained_per_day(self.accounts.challenge)?;
+        let user_pda = self.user_pda()?;
+        // dev : another challenge's locked balance would project a penalty this one never applies
+        Withdraw::validate_user_is_enrolled_in_challenge(
+            self.instruction_data.challenge_id,
+            user_pda.challenge_id,
+        )?;
+        let days_missed = self.instruction_data.days_missed.max(1);
+
+        let locked_balance = user_pda.locked_balance;
+        let locked_balance_penalty = SyncLock::calculate_exponential_penalty_on_locked_balance(
+            locked_balance,
+            days_missed,
+            retained_per_day,
+        )?;
+        let forfeited_deposits = SyncLock::DAILY_LAMPORTS
+            .checked_mul(days_missed as u64)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let total_penalty = forfeited_deposits
+            .checked_add(locked_balance_penalty)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let view = PenaltyView {
+            days_missed,
+            retained_per_day,
+            locked_balance,
+            locked_balance_penalty,
+            forfeited_deposits,
+            total_penalty,
+        };
+
+        set_return_data(view.as_bytes());
+
+        Ok(())
+    }
+
+    pub fn process_rewards(&self) -> ProgramResult {
+        let challenge = Challenge::from_account_info(self.accounts.challenge)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let (winner_rewards, creator_rewards, treasury_profits) =
+            ClaimRewards::calculate_rewards(challenge.total_slashed)?;
+
+        let view = RewardsView {
+            lifecycle: challenge.lifecycle(now)? as u8,
+            total_slashed: challenge.total_slashed,
+            winner_rewards,
+            creator_rewards,
+            treasury_profits,
+            winner: challenge.winner,
+            winning_team: challenge.winning_team,
+            winner_has_claimed: challenge.winner_has_claimed,
+            creator_has_claimed: challenge.creator_has_claimed,
+        };
+
+        set_return_data(view.as_bytes());
+
+        Ok(())
+    }
+
+    fn user_pda(&self) -> Result<User, ProgramError> {
+        let user_pda = self
+            .accounts
+            .remaining_accounts
+            .first()
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        User::from_account_info(user_pda)
+    }
+}
@@ -1,17 +1,20 @@
 use {
     crate::{
-        state::{Challenge, User},
+        events::Event,
+        instructions::ClaimRewards,
+        state::{Challenge, Global, Lifecycle, User},
+        utils::{check_account, check_system_program, AccountCheck, Decoder},
         ScreenWarErrors,
     },
     pinocchio::{
         account_info::AccountInfo,
-        instruction::{Seed, Signer},
         program_error::ProgramError,
-        pubkey::find_program_address,
-        sysvars::{clock::Clock, Sysvar},
+        sysvars::{
+            clock::{Clock, CLOCK_ID},
+            Sysvar,
+        },
         ProgramResult,
     },
-    pinocchio_system::instructions::Transfer,
 };
 
 pub struct Withdraw<'a> {
@@ -29,28 +32,21 @@ pub struct WithdrawAccounts<'a> {
     pub global_bump: u8,
 }
 
+#[derive(Debug, PartialEq)]
 pub struct WithdrawInstructionData {
     pub challenge_id: u32,
 }
 
-impl<'a> TryFrom<(&'a [AccountInfo], &'a [u8])> for Withdraw<'a> {
+impl<'a> TryFrom<(&'a [AccountInfo], WithdrawInstructionData)> for Withdraw<'a> {
     type Error = ProgramError;
 
     fn try_from(
-        (accounts, instruction_data): (&'a [AccountInfo], &'a [u8]),
+        (accounts, instruction_data): (&'a [AccountInfo], WithdrawInstructionData),
     ) -> Result<Self, Self::Error> {
         let accounts = WithdrawAccounts::try_from(accounts)?;
-        let instruction_data = WithdrawInstructionData::try_from(instruction_data)?;
 
         // validate correct challenge pda
-        let (challenge_pda_key, _) = find_program_address(
-            &[b"challenge", &instruction_data.challenge_id.to_le_bytes()],
-            &crate::ID,
-        );
-
-        if challenge_pda_key.ne(accounts.challenge.key()) {
-            return Err(ScreenWarErrors::InvalidChallengePDA.into());
-        }
+        Challenge::validate_pda(accounts.challenge, instruction_data.challenge_id)?;
 
         // return Self
         Ok(Self {
@@ -68,20 +64,16 @@ impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
-        if !user.is_signer() {
-            return Err(ScreenWarErrors::NotSigner.into());
-        }
+        check_account(user, &[AccountCheck::Signer, AccountCheck::Writable])?;
+        check_account(global, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(challenge, &[AccountCheck::Owned])?;
+        check_account(user_pda, &[AccountCheck::Writable, AccountCheck::Owned])?;
+        check_account(clock_sysvar, &[AccountCheck::Address(&CLOCK_ID)])?;
+        check_system_program(system_program)?;
 
-        let (user_pda_key, _) = find_program_address(&[b"user", user.key().as_slice()], &crate::ID);
+        User::validate_pda(user_pda, user.key())?;
 
-        if (&user_pda_key).ne(user_pda.key()) {
-            return Err(ScreenWarErrors::InvalidUserPDA.into());
-        };
-
-        let (global_pda_key, global_bump) = find_program_address(&[b"global"], &crate::ID);
-        if global.key().ne(&global_pda_key) {
-            return Err(ProgramError::InvalidSeeds);
-        };
+        let global_bump = Global::validate_pda(global)?;
 
         Ok(Self {
             user,
@@ -99,11 +91,9 @@ impl<'a> TryFrom<&'a [u8]> for WithdrawInstructionData {
     type Error = ProgramError;
 
     fn try_from(instruction_data: &'a [u8]) -> Result<Self, Self::Error> {
-        if instruction_data.len().ne(&4usize) {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        let challenge_id = u32::from_le_bytes(instruction_data.try_into().unwrap());
+        let mut data = Decoder::new(instruction_data);
+        let challenge_id = data.u32()?;
+        data.finish()?;
 
         Ok(Self { challenge_id })
     }
@@ -114,43 +104,38 @@ impl<'a> Withdraw<'a> {
 
     pub fn process(&mut self) -> ProgramResult {
         // get reference to user and challenge pda datas
-        let mut challenge_raw_data = self.accounts.challenge.try_borrow_mut_data()?;
-        let challenge = Challenge::load_mut(&mut challenge_raw_data)?;
+        let challenge = Challenge::from_account_info(self.accounts.challenge)?;
 
-        let user_pda_raw_data = self.accounts.user_pda.try_borrow_data()?;
-        let user_pda = User::load(&user_pda_raw_data)?;
+        let user_pda = User::from_account_info(self.accounts.user_pda)?;
 
         // validations
-        Self::validate_contention_period_is_over(challenge.end)?;
+        let now = Clock::get()?.unix_timestamp;
+        challenge
+            .lifecycle(now)?
+            .validate(&[Lifecycle::Settlement, Lifecycle::Closed])?;
         Self::validate_user_is_enrolled_in_challenge(
             challenge.challenge_id,
             user_pda.challenge_id,
         )?;
+        Self::validate_team_reward_is_claimed(&challenge, &user_pda)?;
+        ClaimRewards::validate_duel_is_settled(&challenge)?;
 
-        // transfer
-        Self::transfer_sol(
-            self.accounts.global,
-            self.accounts.user,
-            user_pda.locked_balance,
-            self.accounts.global_bump,
-        )?;
-
-        // close user_pda
-        Self::close_user_pda(challenge)?;
+        let locked_balance = user_pda.locked_balance;
+        let refunded_prepaid = user_pda.prepaid_balance;
 
-        Ok(())
-    }
+        // transfer
+        Self::transfer_sol(self.accounts.global, self.accounts.user, locked_balance)?;
 
-    pub fn validate_contention_period_is_over(end: i64) -> ProgramResult {
-        let now = Clock::get()?.unix_timestamp;
-        let five_days = 5 * 24 * 60 * 60;
-        let contention_period = end
-            .checked_add(five_days)
-            .ok_or(ScreenWarErrors::ContentionPhase)?;
+        // close user_pda, refunding rent and any undrawn prepaid stake
+        Self::close_user_pda(self.accounts.user_pda, self.accounts.user)?;
 
-        if contention_period > now {
-            return Err(ScreenWarErrors::ContentionPhase.into());
+        Event::Withdrawn {
+            challenge_id: self.instruction_data.challenge_id,
+            user: *self.accounts.user.key(),
+            locked_balance,
+            refunded_prepaid,
         }
+        .emit();
 
         Ok(())
     }
@@ -166,32 +151,54 @@ impl<'a> Withdraw<'a> {
         Ok(())
     }
 
+    // dev : team rewards are claimed against user_pda, closing it first would forfeit the member's slice
+    pub fn validate_team_reward_is_claimed(
+        challenge: &Challenge,
+        user_pda: &User,
+    ) -> ProgramResult {
+        let winning_member =
+            challenge.winning_team > 0 && user_pda.team_id.eq(&challenge.winning_team);
+
+        if winning_member && !user_pda.team_reward_claimed {
+            return Err(ScreenWarErrors::TeamRewardUnclaimed.into());
+        }
+
+        Ok(())
+    }
+
+    // dev : global pda is owned by this program, so lamports are moved directly without a cpi
     pub fn transfer_sol(
         global: &AccountInfo,
         user: &AccountInfo,
         locked_balance: u64,
-        global_bump: u8,
     ) -> ProgramResult {
         if locked_balance > 0 {
-            let global_bump_binding = [global_bump];
-            let seeds = &[Seed::from(b"global"), Seed::from(&global_bump_binding)];
-            let global_pda_signature = Signer::from(seeds);
-
-            Transfer {
-                from: global,
-                to: user,
-                lamports: locked_balance,
-            }
-            .invoke_signed(&[global_pda_signature])?;
-
-            // dev : checkout pinocchio::instructions::TransferWithSeeds
+            let mut global_lamports = global.try_borrow_mut_lamports()?;
+            *global_lamports = global_lamports
+                .checked_sub(locked_balance)
+                .ok_or(ScreenWarErrors::IntegerUnderflow)?;
+
+            let mut user_lamports = user.try_borrow_mut_lamports()?;
+            *user_lamports = user_lamports
+                .checked_add(locked_balance)
+                .ok_or(ScreenWarErrors::IntegerOverflow)?;
         }
 
         Ok(())
     }
 
-    pub fn close_user_pda(challenge: &mut Challenge) -> ProgramResult {
-        todo!()
-        // Ok(())
+    pub fn close_user_pda(user_pda: &AccountInfo, user: &AccountInfo) -> ProgramResult {
+        // dev : user_pda lamports = rent + prepaid_balance left undrawn by SyncLock
+        {
+            let mut user_pda_lamports = user_pda.try_borrow_mut_lamports()?;
+            let mut user_lamports = user.try_borrow_mut_lamports()?;
+
+            *user_lamports = user_lamports
+                .checked_add(*user_pda_lamports)
+                .ok_or(ScreenWarErrors::IntegerOverflow)?;
+            *user_pda_lamports = 0;
+        }
+
+        user_pda.close()
     }
 }
@@ -1,19 +1,32 @@
 #![no_std]
 
+#[cfg(feature = "client")]
+extern crate std;
+
+pub mod attestation;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod custom_errors;
+pub mod events;
 pub mod instructions;
+#[cfg(feature = "mock-oracle")]
 pub mod mock_oracle;
 pub mod state;
+pub mod utils;
 
 pub use {
+    attestation::*,
     custom_errors::ScreenWarErrors,
+    events::*,
     instructions::*,
-    mock_oracle::*,
     pinocchio::{
-        account_info::AccountInfo, entrypoint, nostd_panic_handler, program_error::ProgramError,
+        account_info::AccountInfo,
+        entrypoint, nostd_panic_handler,
+        program_error::{ProgramError, ToStr},
         ProgramResult,
     },
     state::*,
+    utils::*,
 };
 
 nostd_panic_handler!();
@@ -26,52 +39,108 @@ pub fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    match instruction_data.split_first() {
+    // dev : the runtime only logs the custom code, name it so explorers show something readable
+    dispatch(accounts, instruction_data).inspect_err(|err| {
+        if let ProgramError::Custom(_) = err {
+            pinocchio::log::sol_log(err.to_str::<ScreenWarErrors>());
+        }
+    })
+}
+
+fn dispatch(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+    match ScreenWarsInstruction::unpack(instruction_data)? {
         // initialize
-        Some((Initialize::DISCRIMINATOR, _)) => Initialize::try_from(accounts)?.process()?,
+        ScreenWarsInstruction::Initialize => Initialize::try_from(accounts)?.process()?,
 
         // create challenge
-        Some((CreateChallenge::DISCRIMINATOR, data)) => {
+        ScreenWarsInstruction::CreateChallenge(data) => {
             CreateChallenge::try_from((accounts, data))?.process()?
         }
 
         // join challenge
-        Some((JoinChallenge::DISCRIMINATOR, data)) => {
+        ScreenWarsInstruction::JoinChallenge(data) => {
             JoinChallenge::try_from((accounts, data))?.process()?
         }
 
         // sync and lock
-        Some((SyncLock::DISCRIMINATOR, data)) => SyncLock::try_from((accounts, data))?.process()?,
+        ScreenWarsInstruction::SyncLock(data) => SyncLock::try_from((accounts, data))?.process()?,
 
         // claim winner position
-        Some((ClaimWinnerPosition::DISCRIMINATOR, data)) => {
+        ScreenWarsInstruction::ClaimWinnerPosition(data) => {
             ClaimWinnerPosition::try_from((accounts, data))?.process()?
         }
 
         // withdraw locked funds
-        Some((Withdraw::DISCRIMINATOR, data)) => Withdraw::try_from((accounts, data))?.process()?,
+        ScreenWarsInstruction::Withdraw(data) => Withdraw::try_from((accounts, data))?.process()?,
 
         // claim rewards as winner
-        Some((ClaimRewards::WINNER_REWARD_DISCRIMINATOR, data)) => {
+        ScreenWarsInstruction::ClaimWinnerRewards(data) => {
             ClaimRewards::try_from((accounts, data))?.process_winner_rewards()?
         }
 
         // claim rewards as creator
-        Some((ClaimRewards::CREATOR_REWARD_DISCRIMINATOR, data)) => {
+        ScreenWarsInstruction::ClaimCreatorRewards(data) => {
             ClaimRewards::try_from((accounts, data))?.process_creator_rewards()?
         }
 
+        // claim rewards as winning team member
+        ScreenWarsInstruction::ClaimTeamRewards(data) => {
+            ClaimRewards::try_from((accounts, data))?.process_team_rewards()?
+        }
+
         // take protocol profits (#admin)
-        Some((TakeProfit::DISCRIMINATOR, data)) => {
+        ScreenWarsInstruction::TakeProfit(data) => {
             TakeProfit::try_from((accounts, data))?.process()?
         }
 
         // pause unpause challenge creation (#admin)
-        Some((ToggleChallengeCreation::DISCRIMINATOR, data)) => {
+        ScreenWarsInstruction::ToggleChallengeCreation(data) => {
             ToggleChallengeCreation::try_from((accounts, data))?.process()?
         }
 
-        _ => Err(ProgramError::InvalidInstructionData)?,
+        // update private challenge allowlist (#creator)
+        ScreenWarsInstruction::UpdateAllowlist(data) => {
+            UpdateAllowlist::try_from((accounts, data))?.process()?
+        }
+
+        // settle head-to-head duel stakes
+        ScreenWarsInstruction::SettleDuel(data) => {
+            SettleDuel::try_from((accounts, data))?.process()?
+        }
+
+        // register or revoke a session key for syncing
+        ScreenWarsInstruction::SetSyncDelegate(data) => {
+            SetSyncDelegate::try_from((accounts, data))?.process()?
+        }
+
+        // set batch sync oracle (#admin)
+        ScreenWarsInstruction::SetOracle(data) => {
+            SetOracle::try_from((accounts, data))?.process()?
+        }
+
+        // sync many participants of one challenge (#oracle)
+        ScreenWarsInstruction::BatchSync(data) => {
+            BatchSync::try_from((accounts, data))?.process()?
+        }
+
+        // excuse a range of days in one or every challenge (#admin or #oracle)
+        ScreenWarsInstruction::ExcuseDays(data) => {
+            ExcuseDays::try_from((accounts, data))?.process()?
+        }
+
+        // upgrade an account written by an older program version
+        ScreenWarsInstruction::MigrateAccount => MigrateAccount::try_from(accounts)?.process()?,
+
+        // read-only views, meant to be simulated
+        ScreenWarsInstruction::ViewParticipant(data) => {
+            View::try_from((accounts, data))?.process_participant()?
+        }
+        ScreenWarsInstruction::ViewPenalty(data) => {
+            View::try_from((accounts, data))?.process_penalty()?
+        }
+        ScreenWarsInstruction::ViewRewards(data) => {
+            View::try_from((accounts, data))?.process_rewards()?
+        }
     }
     Ok(())
 }
@@ -1,11 +1,26 @@
 use {
+    crate::{
+        custom_errors::ScreenWarErrors,
+        state::{
+            get_extension, read_v0, validate_header, validate_owner, validate_uninitialized,
+            AccountDiscriminator, Extension, HEADER_LEN,
+        },
+    },
     core::mem::size_of,
-    pinocchio::{program_error::ProgramError, pubkey::Pubkey},
+    pinocchio::{
+        account_info::{AccountInfo, RefMut},
+        program_error::ProgramError,
+        pubkey::{create_program_address, Pubkey},
+    },
 };
 
-#[repr(C)]
-#[derive(Default, Debug)]
+// dev : packed => alignment 1 and no padding, so LEN == size_of::<Self>() and any byte offset casts safely
+// integers are stored in the target's native little-endian order
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug)]
 pub struct Challenge {
+    pub discriminator: u8,
+    pub version: u8,
     pub creator: Pubkey,
     pub challenge_id: u32,
     pub daily_timer: i64,
@@ -17,11 +32,67 @@ pub struct Challenge {
     pub winner_has_claimed: bool,
     pub creator_has_claimed: bool,
     pub total_participants: u32,
+    pub is_private: bool,
+    pub allowlist_root: [u8; 32],
+    pub gate_mint: Pubkey, // dev : default pubkey means challenge is not token gated
+    pub gate_min_balance: u64,
+    pub is_duel: bool,
+    pub opponent: Pubkey,
+    pub duel_stake: u64, // dev : escrowed in the challenge pda itself, paid per duelist at join
+    pub duel_settled: bool,
+    pub team_count: u8, // dev : 0 means individual challenge, team ids are 1..=team_count
+    pub winning_team: u8, // dev : 0 means no team has claimed the winner position yet
+    pub winning_team_score: u64,
     pub bump: u8,
 }
 
+// dev : the first deployment's layout, see read_v0, bools are read as bytes since the account may hold any value
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct ChallengeV0 {
+    creator: Pubkey,
+    challenge_id: u32,
+    daily_timer: i64,
+    start: i64,
+    end: i64,
+    total_slashed: u64,
+    winner: Pubkey,
+    winner_streak: u8,
+    winner_has_claimed: u8,
+    creator_has_claimed: u8,
+    total_participants: u32,
+    bump: u8,
+}
+
 impl Challenge {
-    pub const LEN: usize = size_of::<Pubkey>()
+    pub const LEN: usize = HEADER_LEN
+        + size_of::<Pubkey>()
+        + size_of::<u32>()
+        + size_of::<i64>()
+        + size_of::<i64>()
+        + size_of::<i64>()
+        + size_of::<u64>()
+        + size_of::<Pubkey>()
+        + size_of::<u8>()
+        + size_of::<bool>()
+        + size_of::<bool>()
+        + size_of::<u32>()
+        + size_of::<bool>()
+        + size_of::<[u8; 32]>()
+        + size_of::<Pubkey>()
+        + size_of::<u64>()
+        + size_of::<bool>()
+        + size_of::<Pubkey>()
+        + size_of::<u64>()
+        + size_of::<bool>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<u64>()
+        + size_of::<u8>();
+
+    pub const DISCRIMINATOR: u8 = AccountDiscriminator::Challenge as u8;
+    pub const VERSION: u8 = 1;
+    pub const V0_LEN: usize = size_of::<Pubkey>()
         + size_of::<u32>()
         + size_of::<i64>()
         + size_of::<i64>()
@@ -34,25 +105,127 @@ impl Challenge {
         + size_of::<u32>()
         + size_of::<u8>();
 
+    // dev : init sites spread new() instead of default() so the header is never left zeroed
+    pub fn new() -> Self {
+        Self {
+            discriminator: Self::DISCRIMINATOR,
+            version: Self::VERSION,
+            ..Self::default()
+        }
+    }
+
     pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
-        if bytes.len().ne(&Self::LEN) {
+        if bytes.len() == Self::V0_LEN {
+            return Err(ScreenWarErrors::AccountNeedsMigration.into());
+        }
+        if bytes.len() < Self::LEN {
             return Err(ProgramError::InvalidAccountData);
-        };
+        }
+        validate_header(bytes, AccountDiscriminator::Challenge, Self::VERSION)?;
 
-        let ptr = bytes.as_mut_ptr() as *mut Self; //dev : coersion of (as_mut_ptr)  to (bytes as *mut [u8] as *mut u8) , since bytes itself is fat pointer and contains lenght part too
+        let ptr = bytes.as_mut_ptr() as *mut Self;
         let challenge = unsafe { &mut *ptr };
 
         Ok(challenge)
     }
 
+    // dev : bytes past LEN are the extension region, see extension.rs
     pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
-        if bytes.len().ne(&Self::LEN) {
+        if bytes.len() == Self::V0_LEN {
+            return Err(ScreenWarErrors::AccountNeedsMigration.into());
+        }
+        if bytes.len() < Self::LEN {
             return Err(ProgramError::InvalidAccountData);
-        };
+        }
+        validate_header(bytes, AccountDiscriminator::Challenge, Self::VERSION)?;
 
         let ptr = bytes.as_ptr() as *const Self;
         let challenge = unsafe { &*ptr };
 
         Ok(challenge)
     }
+
+    pub fn init(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() < Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        validate_uninitialized(bytes)?;
+
+        let ptr = bytes.as_mut_ptr() as *mut Self;
+        let challenge = unsafe { &mut *ptr };
+
+        Ok(challenge)
+    }
+
+    // dev : read-only, older versions are upgraded in memory and never written back here
+    pub fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.len() == Self::V0_LEN {
+            return Ok(Self::upgrade_from_v0(bytes));
+        }
+
+        Self::load(bytes).copied()
+    }
+
+    // dev : the 4 padding bytes after challenge_id pushed total_participants and bump past V0_LEN,
+    // so both read as 0, MigrateAccount re-derives the bump while the participant count is lost
+    fn upgrade_from_v0(bytes: &[u8]) -> Self {
+        let legacy: ChallengeV0 = read_v0(bytes);
+
+        Self {
+            creator: legacy.creator,
+            challenge_id: legacy.challenge_id,
+            daily_timer: legacy.daily_timer,
+            start: legacy.start,
+            end: legacy.end,
+            total_slashed: legacy.total_slashed,
+            winner: legacy.winner,
+            winner_streak: legacy.winner_streak,
+            winner_has_claimed: legacy.winner_has_claimed != 0,
+            creator_has_claimed: legacy.creator_has_claimed != 0,
+            total_participants: legacy.total_participants,
+            bump: legacy.bump,
+            ..Self::new()
+        }
+    }
+
+    pub fn extensions(bytes: &[u8]) -> Result<&[u8], ProgramError> {
+        Self::load(bytes)?;
+        Ok(&bytes[Self::LEN..])
+    }
+
+    pub fn extension<T: Extension>(bytes: &[u8]) -> Result<Option<T>, ProgramError> {
+        get_extension(Self::extensions(bytes)?)
+    }
+
+    pub fn from_account_info(account: &AccountInfo) -> Result<Self, ProgramError> {
+        validate_owner(account)?;
+        Self::read(&account.try_borrow_data()?)
+    }
+
+    pub fn from_account_info_mut(account: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        validate_owner(account)?;
+        RefMut::try_map(account.try_borrow_mut_data()?, |bytes| {
+            Self::load_mut(bytes)
+        })
+        .map_err(|(_, err)| err)
+    }
+
+    pub fn validate_pda(account: &AccountInfo, challenge_id: u32) -> Result<u8, ProgramError> {
+        // dev : v0 never persisted its bump, there is nothing to re-derive with until migrated
+        if account.data_len() == Self::V0_LEN {
+            return Err(ScreenWarErrors::AccountNeedsMigration.into());
+        }
+
+        let bump = Self::from_account_info(account)?.bump;
+        let pda = create_program_address(
+            &[b"challenge", &challenge_id.to_le_bytes(), &[bump]],
+            &crate::ID,
+        )?;
+
+        if pda.ne(account.key()) {
+            return Err(ScreenWarErrors::InvalidChallengePDA.into());
+        }
+
+        Ok(bump)
+    }
 }
@@ -0,0 +1,184 @@
+use {
+    crate::{
+        custom_errors::ScreenWarErrors,
+        state::{
+            validate_header, validate_owner, validate_uninitialized, AccountDiscriminator,
+            HEADER_LEN,
+        },
+    },
+    core::mem::size_of,
+    pinocchio::{
+        account_info::{AccountInfo, RefMut},
+        program_error::ProgramError,
+        pubkey::create_program_address,
+    },
+};
+
+// dev : one declared outage as unix timestamps, [start, end)
+// ranges need not be day aligned, touching any second of a challenge day excuses that whole day
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct ExcusedRange {
+    pub start: i64,
+    pub end: i64,
+}
+
+// dev : days the admin or oracle excused, SyncLock settles them as neither pass nor fail
+// one pda per challenge, challenge_id 0 holds the ranges that apply to every challenge
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Excusal {
+    pub discriminator: u8,
+    pub version: u8,
+    pub challenge_id: u32,
+    pub count: u8,
+    pub ranges: [ExcusedRange; Excusal::MAX_RANGES],
+    pub bump: u8,
+}
+
+impl Excusal {
+    pub const MAX_RANGES: usize = 16;
+    pub const GLOBAL_SCOPE: u32 = 0;
+
+    // dev : a sync reads at most the challenge scoped and the global excusal
+    pub const MAX_SCOPES: usize = 2;
+
+    pub const LEN: usize = HEADER_LEN
+        + size_of::<u32>()
+        + size_of::<u8>()
+        + size_of::<ExcusedRange>() * Self::MAX_RANGES
+        + size_of::<u8>();
+
+    pub const DISCRIMINATOR: u8 = AccountDiscriminator::Excusal as u8;
+    pub const VERSION: u8 = 1;
+
+    pub fn new() -> Self {
+        Self {
+            discriminator: Self::DISCRIMINATOR,
+            version: Self::VERSION,
+            ..Self::default()
+        }
+    }
+
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len().ne(&Self::LEN) {
+            return Err(ScreenWarErrors::InvalidPdaDataLen.into());
+        }
+        validate_header(bytes, AccountDiscriminator::Excusal, Self::VERSION)?;
+
+        let ptr = bytes.as_mut_ptr() as *mut Self;
+        let excusal_pda = unsafe { &mut *ptr };
+
+        Ok(excusal_pda)
+    }
+
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len().ne(&Self::LEN) {
+            return Err(ScreenWarErrors::InvalidPdaDataLen.into());
+        }
+        validate_header(bytes, AccountDiscriminator::Excusal, Self::VERSION)?;
+
+        let ptr = bytes.as_ptr() as *const Self;
+        let excusal_pda = unsafe { &*ptr };
+
+        Ok(excusal_pda)
+    }
+
+    pub fn init(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len().ne(&Self::LEN) {
+            return Err(ScreenWarErrors::InvalidPdaDataLen.into());
+        }
+        validate_uninitialized(bytes)?;
+
+        let ptr = bytes.as_mut_ptr() as *mut Self;
+        let excusal_pda = unsafe { &mut *ptr };
+
+        Ok(excusal_pda)
+    }
+
+    pub fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        Self::load(bytes).copied()
+    }
+
+    pub fn from_account_info(account: &AccountInfo) -> Result<Self, ProgramError> {
+        validate_owner(account)?;
+        Self::read(&account.try_borrow_data()?)
+    }
+
+    pub fn from_account_info_mut(account: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        validate_owner(account)?;
+        RefMut::try_map(account.try_borrow_mut_data()?, |bytes| {
+            Self::load_mut(bytes)
+        })
+        .map_err(|(_, err)| err)
+    }
+
+    pub fn validate_pda(account: &AccountInfo, challenge_id: u32) -> Result<u8, ProgramError> {
+        let bump = Self::from_account_info(account)?.bump;
+        let pda = create_program_address(
+            &[b"excusal", &challenge_id.to_le_bytes(), &[bump]],
+            &crate::ID,
+        )?;
+
+        if pda.ne(account.key()) {
+            return Err(ScreenWarErrors::InvalidExcusalPDA.into());
+        }
+
+        Ok(bump)
+    }
+
+    // dev : reads an excusal passed to a sync, only the challenge's own or the global one applies
+    pub fn for_challenge(account: &AccountInfo, challenge_id: u32) -> Result<Self, ProgramError> {
+        let excusal = Self::from_account_info(account)?;
+        let scope = excusal.challenge_id;
+
+        if scope.ne(&challenge_id) && scope.ne(&Self::GLOBAL_SCOPE) {
+            return Err(ScreenWarErrors::InvalidExcusalPDA.into());
+        }
+        Self::validate_pda(account, scope)?;
+
+        Ok(excusal)
+    }
+
+    // dev : ranges overlapping or adjacent to the new one are folded into it, stored ranges stay
+    // disjoint so a repeated or extended outage does not use up another slot
+    pub fn push(&mut self, range: ExcusedRange) -> Result<(), ProgramError> {
+        let mut merged = range;
+        let mut kept = 0;
+
+        for index in 0..self.count as usize {
+            let existing = self.ranges[index];
+
+            if existing.start <= merged.end && merged.start <= existing.end {
+                merged = ExcusedRange {
+                    start: existing.start.min(merged.start),
+                    end: existing.end.max(merged.end),
+                };
+            } else {
+                self.ranges[kept] = existing;
+                kept += 1;
+            }
+        }
+
+        let slot = self
+            .ranges
+            .get_mut(kept)
+            .ok_or(ScreenWarErrors::ExcusedRangesFull)?;
+        *slot = merged;
+
+        let count = self.count as usize;
+        for stale in self.ranges.iter_mut().take(count).skip(kept + 1) {
+            *stale = ExcusedRange::default();
+        }
+        self.count = kept as u8 + 1;
+
+        Ok(())
+    }
+
+    // dev : a day counts as excused when any declared range touches its [start, end) window
+    pub fn excuses(&self, start: i64, end: i64) -> bool {
+        self.ranges[..self.count as usize]
+            .iter()
+            .any(|range| range.start < end && start < range.end)
+    }
+}
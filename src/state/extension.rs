@@ -0,0 +1,178 @@
+use {
+    crate::custom_errors::ScreenWarErrors,
+    core::mem::size_of,
+    pinocchio::{program_error::ProgramError, pubkey::Pubkey},
+};
+
+// dev : optional challenge rules live after the fixed Challenge bytes as
+// [type u16, len u16, value..] entries with little-endian type and len
+pub const EXTENSION_HEADER_LEN: usize = 4;
+
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExtensionType {
+    CustomPenalty = 1,
+    Sponsor = 2,
+}
+
+impl ExtensionType {
+    pub fn from_u16(value: u16) -> Option<Self> {
+        match value {
+            1 => Some(Self::CustomPenalty),
+            2 => Some(Self::Sponsor),
+            _ => None,
+        }
+    }
+}
+
+// dev : implementors are packed, so their bytes are exactly the entry value
+pub trait Extension: Copy {
+    const TYPE: ExtensionType;
+    const LEN: usize = size_of::<Self>();
+
+    fn validate(&self) -> Result<(), ProgramError> {
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct CustomPenalty {
+    pub retained_per_day: u32, // dev : share of locked balance kept per missed day, out of PENALTY_SCALE
+}
+
+impl CustomPenalty {
+    pub const PENALTY_SCALE: u32 = 1_000_000;
+}
+
+impl Extension for CustomPenalty {
+    const TYPE: ExtensionType = ExtensionType::CustomPenalty;
+
+    fn validate(&self) -> Result<(), ProgramError> {
+        if self.retained_per_day > Self::PENALTY_SCALE {
+            return Err(ScreenWarErrors::InvalidExtension.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct Sponsor {
+    pub sponsor: Pubkey,
+    pub info_hash: [u8; 32], // dev : hash of off-chain sponsor metadata
+}
+
+impl Extension for Sponsor {
+    const TYPE: ExtensionType = ExtensionType::Sponsor;
+}
+
+pub struct ExtensionIter<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for ExtensionIter<'a> {
+    type Item = Result<(u16, &'a [u8]), ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        if self.data.len() < EXTENSION_HEADER_LEN {
+            self.data = &[];
+            return Some(Err(ScreenWarErrors::InvalidExtension.into()));
+        }
+
+        let extension_type = u16::from_le_bytes([self.data[0], self.data[1]]);
+        let len = u16::from_le_bytes([self.data[2], self.data[3]]) as usize;
+        let rest = &self.data[EXTENSION_HEADER_LEN..];
+
+        if rest.len() < len {
+            self.data = &[];
+            return Some(Err(ScreenWarErrors::InvalidExtension.into()));
+        }
+
+        let (value, rest) = rest.split_at(len);
+        self.data = rest;
+
+        Some(Ok((extension_type, value)))
+    }
+}
+
+pub fn iter_extensions(region: &[u8]) -> ExtensionIter<'_> {
+    ExtensionIter { data: region }
+}
+
+// dev : unknown types are skipped, they may come from a newer program version;
+// a longer value is read by its known prefix for the same reason
+pub fn get_extension<T: Extension>(region: &[u8]) -> Result<Option<T>, ProgramError> {
+    for entry in iter_extensions(region) {
+        let (extension_type, value) = entry?;
+
+        if extension_type != T::TYPE as u16 {
+            continue;
+        }
+
+        if value.len() < T::LEN {
+            return Err(ScreenWarErrors::InvalidExtension.into());
+        }
+
+        return Ok(Some(unsafe {
+            (value.as_ptr() as *const T).read_unaligned()
+        }));
+    }
+
+    Ok(None)
+}
+
+// dev : stricter than get_extension(), a rule this program can not enforce must not be attached at creation
+pub fn validate_extensions(region: &[u8]) -> Result<(), ProgramError> {
+    let mut seen: u16 = 0;
+
+    for entry in iter_extensions(region) {
+        let (extension_type, value) = entry?;
+
+        let extension_type =
+            ExtensionType::from_u16(extension_type).ok_or(ScreenWarErrors::UnknownExtension)?;
+
+        let bit = 1u16 << (extension_type as u16);
+        if seen & bit != 0 {
+            return Err(ScreenWarErrors::InvalidExtension.into());
+        }
+        seen |= bit;
+
+        match extension_type {
+            ExtensionType::CustomPenalty => validate_value::<CustomPenalty>(value)?,
+            ExtensionType::Sponsor => validate_value::<Sponsor>(value)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_value<T: Extension>(value: &[u8]) -> Result<(), ProgramError> {
+    if value.len() != T::LEN {
+        return Err(ScreenWarErrors::InvalidExtension.into());
+    }
+
+    unsafe { (value.as_ptr() as *const T).read_unaligned() }.validate()
+}
+
+// dev : appends one entry, returns the number of bytes written
+pub fn write_extension<T: Extension>(
+    buffer: &mut [u8],
+    extension: &T,
+) -> Result<usize, ProgramError> {
+    let len = EXTENSION_HEADER_LEN + T::LEN;
+    if buffer.len() < len {
+        return Err(ScreenWarErrors::IntegerBoundsExceeded.into());
+    }
+
+    buffer[0..2].copy_from_slice(&(T::TYPE as u16).to_le_bytes());
+    buffer[2..4].copy_from_slice(&(T::LEN as u16).to_le_bytes());
+    unsafe { (buffer[EXTENSION_HEADER_LEN..].as_mut_ptr() as *mut T).write_unaligned(*extension) };
+
+    Ok(len)
+}
@@ -1,33 +1,82 @@
 use {
+    crate::{
+        custom_errors::ScreenWarErrors,
+        state::{
+            read_v0, validate_header, validate_owner, validate_uninitialized, AccountDiscriminator,
+            HEADER_LEN,
+        },
+    },
     core::mem::size_of,
-    pinocchio::{program_error::ProgramError, pubkey::Pubkey},
+    pinocchio::{
+        account_info::{AccountInfo, RefMut},
+        program_error::ProgramError,
+        pubkey::{create_program_address, Pubkey},
+    },
 };
 
-#[repr(C)]
-#[derive(Default, Debug)]
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug)]
 pub struct Global {
+    pub discriminator: u8,
+    pub version: u8,
     pub admin: Pubkey,
     pub treasury: Pubkey,
     pub treasury_profits: u64,
     pub challenge_ids: u32,
     pub challenge_creation_paused: bool,
     pub bump: u8,
+    pub oracle: Pubkey, // dev : authority allowed to batch sync, default pubkey means none
+}
+
+// dev : the first deployment's layout, see read_v0, bools are read as bytes since the account may hold any value
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct GlobalV0 {
+    admin: Pubkey,
+    treasury: Pubkey,
+    treasury_profits: u64,
+    challenge_ids: u32,
+    challenge_creation_paused: u8,
+    bump: u8,
 }
 
 impl Global {
-    pub const LEN: usize = size_of::<Pubkey>()
+    pub const LEN: usize = HEADER_LEN
+        + size_of::<Pubkey>()
+        + size_of::<Pubkey>()
+        + size_of::<u64>()
+        + size_of::<u32>()
+        + size_of::<bool>()
+        + size_of::<u8>()
+        + size_of::<Pubkey>();
+
+    pub const DISCRIMINATOR: u8 = AccountDiscriminator::Global as u8;
+    pub const VERSION: u8 = 1;
+    pub const V0_LEN: usize = size_of::<Pubkey>()
         + size_of::<Pubkey>()
         + size_of::<u64>()
         + size_of::<u32>()
         + size_of::<bool>()
         + size_of::<u8>();
 
+    pub fn new() -> Self {
+        Self {
+            discriminator: Self::DISCRIMINATOR,
+            version: Self::VERSION,
+            ..Self::default()
+        }
+    }
+
     pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() == Self::V0_LEN {
+            return Err(ScreenWarErrors::AccountNeedsMigration.into());
+        }
         // concise :  use core::mem::transmute; ==> Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
 
         if bytes.len() != Self::LEN {
             return Err(ProgramError::InvalidAccountData);
         }
+        validate_header(bytes, AccountDiscriminator::Global, Self::VERSION)?;
 
         let ptr = bytes.as_mut_ptr() as *mut Self;
         let global = unsafe { &mut *ptr };
@@ -36,13 +85,79 @@ impl Global {
     }
 
     pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() == Self::V0_LEN {
+            return Err(ScreenWarErrors::AccountNeedsMigration.into());
+        }
         if bytes.len() != Self::LEN {
             return Err(ProgramError::InvalidAccountData);
         }
+        validate_header(bytes, AccountDiscriminator::Global, Self::VERSION)?;
 
         let ptr = bytes.as_ptr() as *const Self;
         let global = unsafe { &*ptr };
 
         Ok(global)
     }
+
+    pub fn init(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        validate_uninitialized(bytes)?;
+
+        let ptr = bytes.as_mut_ptr() as *mut Self;
+        let global = unsafe { &mut *ptr };
+
+        Ok(global)
+    }
+
+    // dev : read-only, older versions are upgraded in memory and never written back here
+    pub fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.len() == Self::V0_LEN {
+            return Ok(Self::upgrade_from_v0(bytes));
+        }
+
+        Self::load(bytes).copied()
+    }
+
+    // dev : the oracle did not exist in v0 and reads unset
+    fn upgrade_from_v0(bytes: &[u8]) -> Self {
+        let legacy: GlobalV0 = read_v0(bytes);
+
+        Self {
+            admin: legacy.admin,
+            treasury: legacy.treasury,
+            treasury_profits: legacy.treasury_profits,
+            challenge_ids: legacy.challenge_ids,
+            challenge_creation_paused: legacy.challenge_creation_paused != 0,
+            bump: legacy.bump,
+            ..Self::new()
+        }
+    }
+
+    pub fn from_account_info(account: &AccountInfo) -> Result<Self, ProgramError> {
+        validate_owner(account)?;
+        Self::read(&account.try_borrow_data()?)
+    }
+
+    pub fn from_account_info_mut(account: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        validate_owner(account)?;
+        RefMut::try_map(account.try_borrow_mut_data()?, |bytes| {
+            Self::load_mut(bytes)
+        })
+        .map_err(|(_, err)| err)
+    }
+
+    // dev : re-derives with the stored bump, one create_program_address instead of
+    // find_program_address searching down from 255, returns the bump for signing
+    pub fn validate_pda(account: &AccountInfo) -> Result<u8, ProgramError> {
+        let bump = Self::from_account_info(account)?.bump;
+        let pda = create_program_address(&[b"global", &[bump]], &crate::ID)?;
+
+        if pda.ne(account.key()) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(bump)
+    }
 }
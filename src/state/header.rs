@@ -0,0 +1,89 @@
+use {
+    crate::custom_errors::ScreenWarErrors,
+    core::mem::size_of,
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError},
+};
+
+// dev : every account starts with [discriminator, version], so one account type
+// can never be cast as another and layouts can change without breaking old readers
+pub const HEADER_LEN: usize = 2;
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccountDiscriminator {
+    Global = 1,
+    Challenge = 2,
+    User = 3,
+    Team = 4,
+    Excusal = 5,
+}
+
+pub fn validate_owner(account: &AccountInfo) -> Result<(), ProgramError> {
+    if !account.is_owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    Ok(())
+}
+
+pub fn validate_header(
+    bytes: &[u8],
+    discriminator: AccountDiscriminator,
+    version: u8,
+) -> Result<(), ProgramError> {
+    if bytes[0] != discriminator as u8 {
+        return Err(ScreenWarErrors::InvalidAccountDiscriminator.into());
+    }
+
+    if bytes[1] != version {
+        return Err(ScreenWarErrors::UnsupportedAccountVersion.into());
+    }
+
+    Ok(())
+}
+
+// dev : freshly created accounts are zeroed, a set discriminator means someone initialized it already
+pub fn validate_uninitialized(bytes: &[u8]) -> Result<(), ProgramError> {
+    if bytes[0] != 0 {
+        return Err(ScreenWarErrors::AccountAlreadyInitialized.into());
+    }
+
+    Ok(())
+}
+
+// dev : version 0 is the layout of the first deployment, plain #[repr(C)] structs cast over accounts
+// allocated with the sum of their field sizes, so the bytes follow the C layout with its alignment
+// padding and are cut off at that sum, fields past the cut were never persisted and read as zero
+pub fn read_v0<T: Copy + Default>(bytes: &[u8]) -> T {
+    let mut legacy = T::default();
+    let len = bytes.len().min(size_of::<T>());
+    unsafe {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), &mut legacy as *mut T as *mut u8, len);
+    }
+
+    legacy
+}
+
+// dev : headed layouts only ever grow at the end, so an older version is a prefix of the current one
+pub fn upgrade_prefix<T: Copy + Default>(bytes: &[u8], version: u8) -> T {
+    debug_assert!(bytes.len() >= HEADER_LEN && bytes.len() <= size_of::<T>());
+
+    let mut account = T::default();
+    let ptr = &mut account as *mut T as *mut u8;
+    unsafe {
+        core::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len());
+        ptr.add(1).write(version);
+    }
+
+    account
+}
+
+pub fn write_account<T: Copy>(bytes: &mut [u8], account: T) -> Result<(), ProgramError> {
+    if bytes.len() != size_of::<T>() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    unsafe { (bytes.as_mut_ptr() as *mut T).write_unaligned(account) };
+
+    Ok(())
+}
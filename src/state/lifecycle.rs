@@ -0,0 +1,124 @@
+use {
+    crate::{custom_errors::ScreenWarErrors, state::Challenge},
+    pinocchio::program_error::ProgramError,
+};
+
+// dev : a challenge only ever moves forward through these, ordering follows declaration
+// pending  -> joins and allowlist updates, before start
+// active   -> daily syncs, start..end
+// contention -> winner position race, end..end + CONTENTION_PERIOD
+// settlement -> rewards and withdrawals
+// closed   -> settlement with winner and creator shares paid out
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Lifecycle {
+    Pending,
+    Active,
+    Contention,
+    Settlement,
+    Closed,
+}
+
+impl Lifecycle {
+    pub const CONTENTION_PERIOD: i64 = 5 * 24 * 60 * 60;
+
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Pending),
+            1 => Some(Self::Active),
+            2 => Some(Self::Contention),
+            3 => Some(Self::Settlement),
+            4 => Some(Self::Closed),
+            _ => None,
+        }
+    }
+
+    pub fn of(challenge: &Challenge, now: i64) -> Result<Self, ProgramError> {
+        let (start, end) = (challenge.start, challenge.end);
+        let contention_end = end
+            .checked_add(Self::CONTENTION_PERIOD)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let lifecycle = if now < start {
+            Self::Pending
+        } else if now < end {
+            Self::Active
+        } else if now < contention_end {
+            Self::Contention
+        } else if challenge.winner_has_claimed && challenge.creator_has_claimed {
+            Self::Closed
+        } else {
+            Self::Settlement
+        };
+
+        Ok(lifecycle)
+    }
+
+    // dev : allowed is a contiguous run of phases, the error says on which side of it the challenge is
+    pub fn validate(self, allowed: &[Lifecycle]) -> Result<(), ProgramError> {
+        let (Some(&first), Some(&last)) = (allowed.first(), allowed.last()) else {
+            return Err(ProgramError::InvalidArgument);
+        };
+
+        if allowed.contains(&self) {
+            return Ok(());
+        }
+
+        let err = if self < first {
+            match first {
+                Self::Pending | Self::Active => ScreenWarErrors::ChallengeNotStarted,
+                Self::Contention => ScreenWarErrors::ChallengeNotEnded,
+                Self::Settlement | Self::Closed => ScreenWarErrors::ContentionPhase,
+            }
+        } else {
+            match last {
+                Self::Pending => ScreenWarErrors::ChallengeAlreadyStarted,
+                Self::Active => ScreenWarErrors::ChallengeEnded,
+                Self::Contention => ScreenWarErrors::ContentionExpired,
+                Self::Settlement | Self::Closed => ScreenWarErrors::ChallengeClosed,
+            }
+        };
+
+        Err(err.into())
+    }
+
+    // dev : phases may be skipped when nobody touches the challenge, but never revisited
+    pub fn validate_transition(self, next: Lifecycle) -> Result<(), ProgramError> {
+        if next < self {
+            return Err(ScreenWarErrors::InvalidLifecycleTransition.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl Challenge {
+    pub fn lifecycle(&self, now: i64) -> Result<Lifecycle, ProgramError> {
+        Lifecycle::of(self, now)
+    }
+
+    // dev : 0 based day index since start, the day a sync at `now` reports on
+    pub fn day(&self, now: i64) -> Result<u32, ProgramError> {
+        let one_day = 24 * 60 * 60;
+        let elapsed = now
+            .checked_sub(self.start)
+            .filter(|elapsed| *elapsed >= 0)
+            .ok_or(ScreenWarErrors::ChallengeNotStarted)?;
+
+        u32::try_from(elapsed / one_day).map_err(|_| ProgramError::ArithmeticOverflow)
+    }
+
+    // dev : [start, end) unix timestamps of a day index, the inverse of day()
+    pub fn day_window(&self, day: u32) -> Result<(i64, i64), ProgramError> {
+        let one_day = 24 * 60 * 60;
+        let start = (day as i64)
+            .checked_mul(one_day)
+            .and_then(|offset| self.start.checked_add(offset))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let end = start
+            .checked_add(one_day)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok((start, end))
+    }
+}
@@ -1,5 +1,12 @@
 pub mod challenge;
+pub mod excusal;
+pub mod extension;
 pub mod global;
+pub mod header;
+pub mod lifecycle;
+pub mod team;
 pub mod user;
 
-pub use {challenge::*, global::*, user::*};
+pub use {
+    challenge::*, excusal::*, extension::*, global::*, header::*, lifecycle::*, team::*, user::*,
+};
@@ -0,0 +1,119 @@
+use {
+    crate::{
+        custom_errors::ScreenWarErrors,
+        state::{
+            validate_header, validate_owner, validate_uninitialized, AccountDiscriminator,
+            HEADER_LEN,
+        },
+    },
+    core::mem::size_of,
+    pinocchio::{
+        account_info::{AccountInfo, RefMut},
+        program_error::ProgramError,
+        pubkey::create_program_address,
+    },
+};
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Team {
+    pub discriminator: u8,
+    pub version: u8,
+    pub challenge_id: u32,
+    pub team_id: u8,
+    pub members: u32,
+    pub streak_sum: u64,
+    pub bump: u8,
+}
+
+impl Team {
+    pub const LEN: usize = HEADER_LEN
+        + size_of::<u32>()
+        + size_of::<u8>()
+        + size_of::<u32>()
+        + size_of::<u64>()
+        + size_of::<u8>();
+
+    pub const DISCRIMINATOR: u8 = AccountDiscriminator::Team as u8;
+    pub const VERSION: u8 = 1;
+
+    pub fn new() -> Self {
+        Self {
+            discriminator: Self::DISCRIMINATOR,
+            version: Self::VERSION,
+            ..Self::default()
+        }
+    }
+
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len().ne(&Self::LEN) {
+            return Err(ScreenWarErrors::InvalidPdaDataLen.into());
+        }
+        validate_header(bytes, AccountDiscriminator::Team, Self::VERSION)?;
+
+        let ptr = bytes.as_mut_ptr() as *mut Self;
+        let team_pda = unsafe { &mut *ptr };
+
+        Ok(team_pda)
+    }
+
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len().ne(&Self::LEN) {
+            return Err(ScreenWarErrors::InvalidPdaDataLen.into());
+        }
+        validate_header(bytes, AccountDiscriminator::Team, Self::VERSION)?;
+
+        let ptr = bytes.as_ptr() as *const Self;
+        let team_pda = unsafe { &*ptr };
+
+        Ok(team_pda)
+    }
+
+    pub fn init(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len().ne(&Self::LEN) {
+            return Err(ScreenWarErrors::InvalidPdaDataLen.into());
+        }
+        validate_uninitialized(bytes)?;
+
+        let ptr = bytes.as_mut_ptr() as *mut Self;
+        let team_pda = unsafe { &mut *ptr };
+
+        Ok(team_pda)
+    }
+
+    // dev : teams came after the header, there is no older layout to upgrade
+    pub fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        Self::load(bytes).copied()
+    }
+
+    pub fn from_account_info(account: &AccountInfo) -> Result<Self, ProgramError> {
+        validate_owner(account)?;
+        Self::read(&account.try_borrow_data()?)
+    }
+
+    pub fn from_account_info_mut(account: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        validate_owner(account)?;
+        RefMut::try_map(account.try_borrow_mut_data()?, |bytes| {
+            Self::load_mut(bytes)
+        })
+        .map_err(|(_, err)| err)
+    }
+
+    pub fn validate_pda(
+        account: &AccountInfo,
+        challenge_id: u32,
+        team_id: u8,
+    ) -> Result<u8, ProgramError> {
+        let bump = Self::from_account_info(account)?.bump;
+        let pda = create_program_address(
+            &[b"team", &challenge_id.to_le_bytes(), &[team_id], &[bump]],
+            &crate::ID,
+        )?;
+
+        if pda.ne(account.key()) {
+            return Err(ScreenWarErrors::InvalidTeamPDA.into());
+        }
+
+        Ok(bump)
+    }
+}
@@ -1,45 +1,179 @@
 use {
-    crate::custom_errors::ScreenWarErrors,
+    crate::{
+        custom_errors::ScreenWarErrors,
+        state::{
+            read_v0, upgrade_prefix, validate_header, validate_owner, validate_uninitialized,
+            AccountDiscriminator, HEADER_LEN,
+        },
+    },
     core::mem::size_of,
-    pinocchio::{program_error::ProgramError, pubkey::Pubkey},
+    pinocchio::{
+        account_info::{AccountInfo, RefMut},
+        program_error::ProgramError,
+        pubkey::{create_program_address, Pubkey},
+    },
 };
 
-#[repr(C)]
-#[derive(Default, Debug)]
+#[repr(C, packed)]
+#[derive(Clone, Copy, Default, Debug)]
 pub struct User {
+    pub discriminator: u8,
+    pub version: u8,
     pub user: Pubkey,
     pub challenge_id: u32,
     pub locked_balance: u64,
+    pub prepaid_balance: u64, // dev : undrawn stake prepaid at join, escrowed in this pda
     pub streak: u8,
+    pub team_id: u8, // dev : 0 means not in a team
+    pub team_reward_claimed: bool,
+    pub delegate: Pubkey, // dev : session key allowed to sync, default pubkey means none
+    pub delegate_expiry: i64,
     pub bump: u8,
+    pub device: Pubkey, // dev : appended in v2 so v1 stays a prefix, default pubkey means no device bound
+    pub next_sync_day: u32, // dev : appended in v3, day index since start the next attestation must cover
+}
+
+// dev : the first deployment's layout, see read_v0
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct UserV0 {
+    user: Pubkey,
+    challenge_id: u32,
+    locked_balance: u64,
+    streak: u8,
+    bump: u8,
 }
 
 impl User {
-    pub const LEN: usize = size_of::<Pubkey>()
+    pub const LEN: usize = HEADER_LEN
+        + size_of::<Pubkey>()
+        + size_of::<u32>()
+        + size_of::<u64>()
+        + size_of::<u64>()
+        + size_of::<u8>()
+        + size_of::<u8>()
+        + size_of::<bool>()
+        + size_of::<Pubkey>()
+        + size_of::<i64>()
+        + size_of::<u8>()
+        + size_of::<Pubkey>()
+        + size_of::<u32>();
+
+    pub const DISCRIMINATOR: u8 = AccountDiscriminator::User as u8;
+    pub const VERSION: u8 = 3;
+    pub const V2_LEN: usize = Self::LEN - size_of::<u32>();
+    pub const V1_LEN: usize = Self::V2_LEN - size_of::<Pubkey>();
+    pub const V0_LEN: usize = size_of::<Pubkey>()
         + size_of::<u32>()
         + size_of::<u64>()
         + size_of::<u8>()
         + size_of::<u8>();
 
+    pub fn new() -> Self {
+        Self {
+            discriminator: Self::DISCRIMINATOR,
+            version: Self::VERSION,
+            ..Self::default()
+        }
+    }
+
     pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if [Self::V0_LEN, Self::V1_LEN, Self::V2_LEN].contains(&bytes.len()) {
+            return Err(ScreenWarErrors::AccountNeedsMigration.into());
+        }
         if bytes.len().ne(&Self::LEN) {
             return Err(ScreenWarErrors::InvalidPdaDataLen.into());
         }
-        let ptr = bytes.as_mut_ptr() as *mut Self;
+        validate_header(bytes, AccountDiscriminator::User, Self::VERSION)?;
 
+        let ptr = bytes.as_mut_ptr() as *mut Self;
         let user_pda = unsafe { &mut *ptr };
 
         Ok(user_pda)
     }
 
     pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if [Self::V0_LEN, Self::V1_LEN, Self::V2_LEN].contains(&bytes.len()) {
+            return Err(ScreenWarErrors::AccountNeedsMigration.into());
+        }
         if bytes.len().ne(&Self::LEN) {
             return Err(ScreenWarErrors::InvalidPdaDataLen.into());
         }
-        let ptr = bytes.as_ptr() as *const Self;
+        validate_header(bytes, AccountDiscriminator::User, Self::VERSION)?;
 
+        let ptr = bytes.as_ptr() as *const Self;
         let user_pda = unsafe { &*ptr };
 
         Ok(user_pda)
     }
+
+    pub fn init(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len().ne(&Self::LEN) {
+            return Err(ScreenWarErrors::InvalidPdaDataLen.into());
+        }
+        validate_uninitialized(bytes)?;
+
+        let ptr = bytes.as_mut_ptr() as *mut Self;
+        let user_pda = unsafe { &mut *ptr };
+
+        Ok(user_pda)
+    }
+
+    // dev : read-only, older versions are upgraded in memory and never written back here
+    // dev : next_sync_day was not tracked before v3 and reads as 0, MigrateAccount sets it from the challenge
+    pub fn read(bytes: &[u8]) -> Result<Self, ProgramError> {
+        let version = match bytes.len() {
+            Self::V0_LEN => return Ok(Self::upgrade_from_v0(bytes)),
+            Self::V1_LEN => 1,
+            Self::V2_LEN => 2,
+            _ => return Self::load(bytes).copied(),
+        };
+
+        validate_header(bytes, AccountDiscriminator::User, version)?;
+        Ok(upgrade_prefix(bytes, Self::VERSION))
+    }
+
+    // dev : the 4 padding bytes after challenge_id cut the top 2 bytes of locked_balance and pushed
+    // streak and bump past V0_LEN, all read as 0, MigrateAccount re-derives the bump
+    fn upgrade_from_v0(bytes: &[u8]) -> Self {
+        let legacy: UserV0 = read_v0(bytes);
+
+        Self {
+            user: legacy.user,
+            challenge_id: legacy.challenge_id,
+            locked_balance: legacy.locked_balance,
+            streak: legacy.streak,
+            bump: legacy.bump,
+            ..Self::new()
+        }
+    }
+
+    pub fn from_account_info(account: &AccountInfo) -> Result<Self, ProgramError> {
+        validate_owner(account)?;
+        Self::read(&account.try_borrow_data()?)
+    }
+
+    pub fn from_account_info_mut(account: &AccountInfo) -> Result<RefMut<'_, Self>, ProgramError> {
+        validate_owner(account)?;
+        RefMut::try_map(account.try_borrow_mut_data()?, |bytes| {
+            Self::load_mut(bytes)
+        })
+        .map_err(|(_, err)| err)
+    }
+
+    pub fn validate_pda(account: &AccountInfo, user: &Pubkey) -> Result<u8, ProgramError> {
+        // dev : v0 never persisted its bump, there is nothing to re-derive with until migrated
+        if account.data_len() == Self::V0_LEN {
+            return Err(ScreenWarErrors::AccountNeedsMigration.into());
+        }
+
+        let bump = Self::from_account_info(account)?.bump;
+        let pda = create_program_address(&[b"user", user.as_slice(), &[bump]], &crate::ID)?;
+
+        if pda.ne(account.key()) {
+            return Err(ScreenWarErrors::InvalidUserPDA.into());
+        }
+
+        Ok(bump)
+    }
 }
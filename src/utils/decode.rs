@@ -0,0 +1,112 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+// dev : reads one trailing Option<T> section of `len` bytes, returns it with the unread bytes
+// [] => None (omitted) | [0, ..] => None | [1, value.., ..] => Some(value)
+pub fn split_option(data: &[u8], len: usize) -> Result<(Option<&[u8]>, &[u8]), ProgramError> {
+    match data {
+        [] => Ok((None, data)),
+        [0, rest @ ..] => Ok((None, rest)),
+        [1, rest @ ..] if rest.len() >= len => {
+            let (value, rest) = rest.split_at(len);
+            Ok((Some(value), rest))
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+// dev : reads one trailing bool flag section, same encoding as split_option without a value
+// [] => false (omitted) | [0, ..] => false | [1, ..] => true
+pub fn split_flag(data: &[u8]) -> Result<(bool, &[u8]), ProgramError> {
+    let (flag, rest) = split_option(data, 0)?;
+    Ok((flag.is_some(), rest))
+}
+
+// dev : cursor over instruction data, every read is bounds checked
+// short payloads, bad tags and leftover bytes all surface as InvalidInstructionData, never a panic
+pub struct Decoder<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], ProgramError> {
+        if self.data.len() < len {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (value, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(value)
+    }
+
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], ProgramError> {
+        self.take(N)?
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)
+    }
+
+    pub fn u8(&mut self) -> Result<u8, ProgramError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    // dev : only 0 and 1, anything else is a malformed payload rather than true
+    pub fn bool(&mut self) -> Result<bool, ProgramError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    pub fn u16(&mut self) -> Result<u16, ProgramError> {
+        Ok(u16::from_le_bytes(self.array()?))
+    }
+
+    pub fn u32(&mut self) -> Result<u32, ProgramError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    pub fn u64(&mut self) -> Result<u64, ProgramError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+
+    pub fn i64(&mut self) -> Result<i64, ProgramError> {
+        Ok(i64::from_le_bytes(self.array()?))
+    }
+
+    pub fn pubkey(&mut self) -> Result<Pubkey, ProgramError> {
+        self.array()
+    }
+
+    pub fn option(&mut self, len: usize) -> Result<Option<&'a [u8]>, ProgramError> {
+        let (value, rest) = split_option(self.data, len)?;
+        self.data = rest;
+        Ok(value)
+    }
+
+    pub fn flag(&mut self) -> Result<bool, ProgramError> {
+        let (flag, rest) = split_flag(self.data)?;
+        self.data = rest;
+        Ok(flag)
+    }
+
+    // dev : hands out every unread byte, for trailing variable length sections
+    pub fn rest(&mut self) -> &'a [u8] {
+        core::mem::take(&mut self.data)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn finish(self) -> Result<(), ProgramError> {
+        if !self.data.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(())
+    }
+}
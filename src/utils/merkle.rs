@@ -0,0 +1,69 @@
+use pinocchio::pubkey::Pubkey;
+
+pub const MERKLE_NODE_LEN: usize = 32;
+pub const MAX_MERKLE_PROOF_DEPTH: usize = 16;
+
+// dev : domain separation so an inner node can never be replayed as a leaf (second preimage)
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub fn hashv(vals: &[&[u8]]) -> [u8; 32] {
+    #[cfg(target_os = "solana")]
+    {
+        let mut hash = core::mem::MaybeUninit::<[u8; 32]>::uninit();
+
+        unsafe {
+            pinocchio::syscalls::sol_sha256(
+                vals as *const _ as *const u8,
+                vals.len() as u64,
+                hash.as_mut_ptr() as *mut u8,
+            );
+
+            hash.assume_init()
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        for val in vals {
+            hasher.update(val);
+        }
+        hasher.finalize().into()
+    }
+}
+
+pub fn hash_leaf(wallet: &Pubkey) -> [u8; 32] {
+    hashv(&[LEAF_PREFIX, wallet])
+}
+
+// dev : pairs are sorted before hashing, so proofs don't need to carry left/right flags
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[NODE_PREFIX, a, b])
+    } else {
+        hashv(&[NODE_PREFIX, b, a])
+    }
+}
+
+// dev : proof is the raw concatenation of 32 byte sibling nodes, from leaf level up to the root
+pub fn is_valid_merkle_proof_len(proof: &[u8]) -> bool {
+    proof.chunks_exact(MERKLE_NODE_LEN).remainder().is_empty()
+        && proof.len() / MERKLE_NODE_LEN <= MAX_MERKLE_PROOF_DEPTH
+}
+
+pub fn verify_merkle_proof(root: &[u8; 32], wallet: &Pubkey, proof: &[u8]) -> bool {
+    if !is_valid_merkle_proof_len(proof) {
+        return false;
+    }
+
+    let computed = proof
+        .chunks_exact(MERKLE_NODE_LEN)
+        .fold(hash_leaf(wallet), |node, sibling| {
+            hash_pair(&node, sibling.try_into().unwrap())
+        });
+
+    computed.eq(root)
+}
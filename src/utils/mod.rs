@@ -0,0 +1,6 @@
+pub mod decode;
+pub mod merkle;
+pub mod token;
+pub mod validation;
+
+pub use {decode::*, merkle::*, token::*, validation::*};
@@ -0,0 +1,56 @@
+use {
+    crate::ScreenWarErrors,
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+};
+
+pub const TOKEN_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("TokenzQdBNbLqP5VEhdkAS5EN5JYTGy8oAPx3gF2qvk");
+
+// dev : spl token account layout
+// mint   : bytes[0..32]
+// owner  : bytes[32..64]
+// amount : bytes[64..72]
+// state  : bytes[108]       (0 = uninitialized, 1 = initialized, 2 = frozen)
+// token-2022 accounts may carry extensions after byte 165, tagged with account type 2 at byte[165]
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+const TOKEN_ACCOUNT_STATE_OFFSET: usize = 108;
+const TOKEN_2022_ACCOUNT_TYPE: u8 = 2;
+
+pub struct TokenAccountView {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+impl TokenAccountView {
+    pub fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
+        let is_token_program = account.is_owned_by(&TOKEN_PROGRAM_ID);
+        let is_token_2022_program = account.is_owned_by(&TOKEN_2022_PROGRAM_ID);
+
+        if !is_token_program && !is_token_2022_program {
+            return Err(ScreenWarErrors::InvalidTokenAccount.into());
+        }
+
+        let data = account.try_borrow_data()?;
+
+        let valid_len = match data.len() {
+            TOKEN_ACCOUNT_LEN => true,
+            len if len > TOKEN_ACCOUNT_LEN => {
+                is_token_2022_program && data[TOKEN_ACCOUNT_LEN] == TOKEN_2022_ACCOUNT_TYPE
+            }
+            _ => false,
+        };
+
+        if !valid_len || data[TOKEN_ACCOUNT_STATE_OFFSET] == 0 {
+            return Err(ScreenWarErrors::InvalidTokenAccount.into());
+        }
+
+        Ok(Self {
+            mint: data[0..32].try_into().unwrap(),
+            owner: data[32..64].try_into().unwrap(),
+            amount: u64::from_le_bytes(data[64..72].try_into().unwrap()),
+        })
+    }
+}
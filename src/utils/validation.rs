@@ -0,0 +1,57 @@
+use {
+    crate::ScreenWarErrors,
+    pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey},
+};
+
+// dev : what an instruction expects of one account, listed next to the account in *Accounts::try_from()
+// pda checks stay with the state types, see Global::validate_pda() and friends
+#[derive(Clone, Copy, Debug)]
+pub enum AccountCheck<'a> {
+    Signer,
+    Writable,
+    Owned,
+    Address(&'a Pubkey),
+}
+
+pub fn check_account(account: &AccountInfo, checks: &[AccountCheck]) -> Result<(), ProgramError> {
+    for check in checks {
+        match check {
+            AccountCheck::Signer if !account.is_signer() => {
+                return Err(ScreenWarErrors::NotSigner.into())
+            }
+            AccountCheck::Writable if !account.is_writable() => {
+                return Err(ScreenWarErrors::AccountNotWritable.into())
+            }
+            AccountCheck::Owned if !account.is_owned_by(&crate::ID) => {
+                return Err(ProgramError::InvalidAccountOwner)
+            }
+            AccountCheck::Address(address) if account.key().ne(*address) => {
+                return Err(ScreenWarErrors::InvalidAccountAddress.into())
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+pub fn check_system_program(account: &AccountInfo) -> Result<(), ProgramError> {
+    check_account(account, &[AccountCheck::Address(&pinocchio_system::ID)])
+}
+
+// dev : the same state account passed twice would let one write shadow the other
+pub fn check_unique<'b, I>(accounts: I) -> Result<(), ProgramError>
+where
+    I: IntoIterator<Item = &'b AccountInfo>,
+    I::IntoIter: Clone,
+{
+    let mut accounts = accounts.into_iter();
+
+    while let Some(account) = accounts.next() {
+        if accounts.clone().any(|other| other.key().eq(account.key())) {
+            return Err(ScreenWarErrors::DuplicateAccount.into());
+        }
+    }
+
+    Ok(())
+}
@@ -1,15 +1,29 @@
+#![allow(
+    dead_code,
+    non_upper_case_globals,
+    clippy::missing_safety_doc,
+    clippy::not_unsafe_ptr_arg_deref
+)]
+
 pub use {
     litesvm::LiteSVM,
-    screenwars_pinocchio::{Challenge, Global, User, ID},
+    screenwars_pinocchio::{
+        client, hash_leaf, hash_pair, Challenge, DeviceReport, Global, ProgramError,
+        SyncAttestation, Team, User, ID, TOKEN_PROGRAM_ID,
+    },
+    solana_account::Account,
     solana_clock::Clock,
     solana_instruction::{AccountMeta, Instruction},
     solana_keypair::Keypair,
     solana_kite::{get_pda_and_bump, send_transaction_from_instructions, SolanaKiteError},
-    solana_program::{system_program::ID as SYSTEM_ID, sysvar::rent::ID as RENT_ID},
+    solana_program::{
+        system_program::ID as SYSTEM_ID,
+        sysvar::{clock::ID as CLOCK_ID, instructions::ID as INSTRUCTIONS_ID, rent::ID as RENT_ID},
+    },
     solana_pubkey::Pubkey,
     solana_signer::Signer,
+    solana_transaction::Transaction,
 };
-use {pinocchio::instruction, solana_program::native_token::Sol};
 
 pub const JAN_2025: i64 = 1735689600;
 pub const two_hours: i64 = 2 * (60 * 60);
@@ -25,6 +39,7 @@ pub struct Env {
     pub jeff: Keypair,
     pub berg: Keypair,
     pub shrinath: Keypair,
+    pub oracle: Keypair, // dev : registered by execute_initialize(), signs sync attestations
 }
 
 pub fn map_actor_from_id<'a>(env: *const Env, actor: &str) -> &'a Keypair {
@@ -69,12 +84,14 @@ pub fn setup_escrow_test() -> Env {
     let jeff = Keypair::new();
     let berg = Keypair::new();
     let shrinath = Keypair::new();
+    let oracle = Keypair::new();
 
     // fund keypairs
     litesvm.airdrop(&admin.pubkey(), 1_000_000_000).unwrap();
     litesvm.airdrop(&jeff.pubkey(), 1_000_000_000).unwrap();
     litesvm.airdrop(&berg.pubkey(), 1_000_000_000).unwrap();
     litesvm.airdrop(&shrinath.pubkey(), 1_000_000_000).unwrap();
+    litesvm.airdrop(&oracle.pubkey(), 1_000_000_000).unwrap();
 
     let mut env = Env {
         litesvm,
@@ -83,6 +100,7 @@ pub fn setup_escrow_test() -> Env {
         jeff,
         berg,
         shrinath,
+        oracle,
     };
 
     // set initial timestamp to Jan 1 2025
@@ -135,10 +153,11 @@ pub fn execute_initialize(env: &mut Env) -> Result<Pubkey, SolanaKiteError> {
     let (global_pda, _) = get_pda_and_bump(&[b"global".as_ref().into()], &env.program_id);
     let accounts = build_initialize_accounts(env.admin.pubkey(), global_pda);
     let instructions = build_initialize_instruction(accounts);
+    let set_oracle = build_set_oracle_instruction(env.admin.pubkey(), env.oracle.pubkey());
 
     send_transaction_from_instructions(
         &mut env.litesvm,
-        vec![instructions],
+        vec![instructions, set_oracle],
         &[&env.admin],
         &env.admin.pubkey(),
     )?;
@@ -180,9 +199,22 @@ pub fn build_create_challenge_accounts(
     }
 }
 
+// dev : optional challenge rules, encoded as trailing Option<T> sections of create challenge data
+// custom_penalty and sponsor are encoded as tlv extensions after them
+#[derive(Clone, Copy, Default)]
+pub struct ChallengeOptions {
+    pub allowlist_root: Option<[u8; 32]>,
+    pub token_gate: Option<(Pubkey, u64)>,
+    pub duel: Option<(Pubkey, u64)>,
+    pub team_count: Option<u8>,
+    pub custom_penalty: Option<u32>,
+    pub sponsor: Option<(Pubkey, [u8; 32])>,
+}
+
 pub fn build_create_challenge_instruction(
     start_time: i64,
     daily_timer: i64,
+    options: ChallengeOptions,
     create_challenge_accounts: CreateChallengeAccounts,
 ) -> Instruction {
     let program_id = get_program_id();
@@ -198,6 +230,42 @@ pub fn build_create_challenge_instruction(
     let mut data = vec![1u8]; // discriminator
     data.extend_from_slice(&start_time.to_le_bytes());
     data.extend_from_slice(&daily_timer.to_le_bytes());
+    match options.allowlist_root {
+        Some(root) => {
+            data.push(1);
+            data.extend_from_slice(&root);
+        }
+        None => data.push(0),
+    }
+    match options.token_gate {
+        Some((mint, min_balance)) => {
+            data.push(1);
+            data.extend_from_slice(mint.as_ref());
+            data.extend_from_slice(&min_balance.to_le_bytes());
+        }
+        None => data.push(0),
+    }
+    match options.duel {
+        Some((opponent, stake)) => {
+            data.push(1);
+            data.extend_from_slice(opponent.as_ref());
+            data.extend_from_slice(&stake.to_le_bytes());
+        }
+        None => data.push(0),
+    }
+    match options.team_count {
+        Some(team_count) => data.extend_from_slice(&[1, team_count]),
+        None => data.push(0),
+    }
+    if let Some(retained_per_day) = options.custom_penalty {
+        data.extend_from_slice(&[1, 0, 4, 0]);
+        data.extend_from_slice(&retained_per_day.to_le_bytes());
+    }
+    if let Some((sponsor, info_hash)) = options.sponsor {
+        data.extend_from_slice(&[2, 0, 64, 0]);
+        data.extend_from_slice(sponsor.as_ref());
+        data.extend_from_slice(&info_hash);
+    }
 
     Instruction {
         program_id,
@@ -212,10 +280,29 @@ pub fn execute_create_challenge(
     challenge_id: u32, // dev : must be equal to current global.challenge_ids
     start_time: i64,
     daily_timer: i64,
+) -> Result<(Pubkey, Pubkey), SolanaKiteError> {
+    execute_create_challenge_with_options(
+        env,
+        creator_actor,
+        challenge_id,
+        start_time,
+        daily_timer,
+        ChallengeOptions::default(),
+    )
+}
+
+pub fn execute_create_challenge_with_options(
+    env: &mut Env,
+    creator_actor: &str,
+    challenge_id: u32,
+    start_time: i64,
+    daily_timer: i64,
+    options: ChallengeOptions,
 ) -> Result<(Pubkey, Pubkey), SolanaKiteError> {
     let creator = map_actor_from_id(&*env, creator_actor);
     let accounts = build_create_challenge_accounts(creator.pubkey(), challenge_id);
-    let instructions = build_create_challenge_instruction(start_time, daily_timer, accounts);
+    let instructions =
+        build_create_challenge_instruction(start_time, daily_timer, options, accounts);
 
     send_transaction_from_instructions(
         &mut env.litesvm,
@@ -258,23 +345,44 @@ pub fn build_join_challenge_accounts(user: Pubkey, challenge_id: u32) -> JoinCha
     }
 }
 
+#[derive(Clone, Default)]
+pub struct JoinOptions {
+    pub allowlist_proof: Vec<[u8; 32]>,
+    pub gate_token_account: Option<Pubkey>,
+    pub team_id: Option<u8>,
+    pub prepay: bool,
+    pub device: Option<Pubkey>,
+}
+
 pub fn build_join_challenge_instruction(
-    env: &mut Env,
+    _env: &mut Env,
     accounts: JoinChallengeAccounts,
     challenge_id: u32,
+    options: &JoinOptions,
 ) -> Instruction {
     let program_id = get_program_id();
 
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(accounts.user, true),
         AccountMeta::new(accounts.challenge, false),
         AccountMeta::new(accounts.user_pda, false),
         AccountMeta::new_readonly(accounts.rent_sysvar, false),
         AccountMeta::new_readonly(accounts.system_program, false),
     ];
+    if let Some(token_account) = options.gate_token_account {
+        accounts.push(AccountMeta::new_readonly(token_account, false));
+    }
+    if let Some(team_id) = options.team_id {
+        accounts.push(AccountMeta::new(get_team_pda(challenge_id, team_id), false));
+    }
 
     let mut data = vec![2u8];
     data.extend_from_slice(&challenge_id.to_le_bytes());
+    data.extend_from_slice(&[options.team_id.unwrap_or(0), options.prepay as u8]);
+    data.extend_from_slice(options.device.unwrap_or_default().as_ref());
+    for node in &options.allowlist_proof {
+        data.extend_from_slice(node);
+    }
 
     Instruction {
         program_id,
@@ -287,10 +395,19 @@ pub fn execute_join_challenge(
     env: &mut Env,
     user_actor: &str,
     challenge_id: u32,
-) -> Result<(Pubkey), SolanaKiteError> {
+) -> Result<Pubkey, SolanaKiteError> {
+    execute_join_challenge_with_options(env, user_actor, challenge_id, &JoinOptions::default())
+}
+
+pub fn execute_join_challenge_with_options(
+    env: &mut Env,
+    user_actor: &str,
+    challenge_id: u32,
+    options: &JoinOptions,
+) -> Result<Pubkey, SolanaKiteError> {
     let user = map_actor_from_id(env, user_actor);
     let accounts = build_join_challenge_accounts(user.pubkey(), challenge_id);
-    let instructions = build_join_challenge_instruction(env, accounts, challenge_id);
+    let instructions = build_join_challenge_instruction(env, accounts, challenge_id, options);
 
     send_transaction_from_instructions(
         &mut env.litesvm,
@@ -301,3 +418,756 @@ pub fn execute_join_challenge(
 
     Ok(accounts.user_pda)
 }
+
+pub fn build_update_allowlist_instruction(
+    creator: Pubkey,
+    challenge_id: u32,
+    allowlist_root: [u8; 32],
+) -> Instruction {
+    let program_id = get_program_id();
+    let (challenge, _) = get_pda_and_bump(
+        &[
+            b"challenge".as_ref().into(),
+            challenge_id.to_le_bytes().as_ref().into(),
+        ],
+        &program_id,
+    );
+
+    let accounts = vec![
+        AccountMeta::new(creator, true),
+        AccountMeta::new(challenge, false),
+    ];
+
+    let mut data = vec![10u8];
+    data.extend_from_slice(&challenge_id.to_le_bytes());
+    data.extend_from_slice(&allowlist_root);
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn execute_update_allowlist(
+    env: &mut Env,
+    creator_actor: &str,
+    challenge_id: u32,
+    allowlist_root: [u8; 32],
+) -> Result<(), SolanaKiteError> {
+    let creator = map_actor_from_id(env, creator_actor);
+    let instruction =
+        build_update_allowlist_instruction(creator.pubkey(), challenge_id, allowlist_root);
+
+    send_transaction_from_instructions(
+        &mut env.litesvm,
+        vec![instruction],
+        &[creator],
+        &creator.pubkey(),
+    )?;
+
+    Ok(())
+}
+
+// dev : returns merkle root and per wallet proofs, odd nodes are promoted to the next level unchanged
+pub fn build_allowlist(wallets: &[Pubkey]) -> ([u8; 32], Vec<Vec<[u8; 32]>>) {
+    let mut level: Vec<[u8; 32]> = wallets
+        .iter()
+        .map(|wallet| hash_leaf(&wallet.to_bytes()))
+        .collect();
+    let mut positions: Vec<usize> = (0..wallets.len()).collect();
+    let mut proofs: Vec<Vec<[u8; 32]>> = vec![vec![]; wallets.len()];
+
+    while level.len() > 1 {
+        for (proof, position) in proofs.iter_mut().zip(positions.iter_mut()) {
+            let sibling = *position ^ 1;
+            if sibling < level.len() {
+                proof.push(level[sibling]);
+            }
+            *position /= 2;
+        }
+
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => hash_pair(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+
+    (level[0], proofs)
+}
+
+// dev : writes a raw spl token account straight into the svm, no mint or token program setup needed
+pub fn set_token_account(env: &mut Env, owner: Pubkey, mint: Pubkey, amount: u64) -> Pubkey {
+    let token_account = Pubkey::new_unique();
+
+    let mut data = vec![0u8; 165];
+    data[0..32].copy_from_slice(mint.as_ref());
+    data[32..64].copy_from_slice(owner.as_ref());
+    data[64..72].copy_from_slice(&amount.to_le_bytes());
+    data[108] = 1; // initialized
+
+    env.litesvm
+        .set_account(
+            token_account,
+            Account {
+                lamports: 2_039_280,
+                data,
+                owner: Pubkey::from(TOKEN_PROGRAM_ID),
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    token_account
+}
+
+pub fn get_challenge_pda(challenge_id: u32) -> Pubkey {
+    let (challenge_pda, _) = get_pda_and_bump(
+        &[
+            b"challenge".as_ref().into(),
+            challenge_id.to_le_bytes().as_ref().into(),
+        ],
+        &get_program_id(),
+    );
+    challenge_pda
+}
+
+pub fn get_user_pda(user: Pubkey) -> Pubkey {
+    let (user_pda, _) =
+        get_pda_and_bump(&[b"user".as_ref().into(), user.into()], &get_program_id());
+    user_pda
+}
+
+pub fn get_team_pda(challenge_id: u32, team_id: u8) -> Pubkey {
+    let (team_pda, _) = get_pda_and_bump(
+        &[
+            b"team".as_ref().into(),
+            challenge_id.to_le_bytes().as_ref().into(),
+            [team_id].as_ref().into(),
+        ],
+        &get_program_id(),
+    );
+    team_pda
+}
+
+// dev : challenge_id 0 is the excusal applying to every challenge
+pub fn get_excusal_pda(challenge_id: u32) -> Pubkey {
+    let (excusal_pda, _) = get_pda_and_bump(
+        &[
+            b"excusal".as_ref().into(),
+            challenge_id.to_le_bytes().as_ref().into(),
+        ],
+        &get_program_id(),
+    );
+    excusal_pda
+}
+
+// dev : debug_data mirrors DebugData => (user_passed, days_not_synced, synced_today)
+pub fn build_sync_lock_instruction(
+    user: Pubkey,
+    challenge_id: u32,
+    team_id: Option<u8>,
+    debug_data: Option<(bool, u8, bool)>,
+) -> Instruction {
+    let program_id = get_program_id();
+    let (global_pda, _) = get_pda_and_bump(&[b"global".as_ref().into()], &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new(user, true),
+        AccountMeta::new(global_pda, false),
+        AccountMeta::new(get_challenge_pda(challenge_id), false),
+        AccountMeta::new(get_user_pda(user), false),
+        AccountMeta::new_readonly(Pubkey::from(CLOCK_ID.to_bytes()), false),
+        AccountMeta::new_readonly(Pubkey::from(SYSTEM_ID.to_bytes()), false),
+        AccountMeta::new_readonly(Pubkey::from(INSTRUCTIONS_ID.to_bytes()), false),
+    ];
+    if let Some(team_id) = team_id {
+        accounts.push(AccountMeta::new(get_team_pda(challenge_id, team_id), false));
+    }
+
+    let mut data = vec![9u8];
+    data.extend_from_slice(&challenge_id.to_le_bytes());
+    match debug_data {
+        Some((user_passed, days_not_synced, synced_today)) => {
+            data.extend_from_slice(&[1, user_passed as u8, days_not_synced, synced_today as u8])
+        }
+        None => data.extend_from_slice(&[0, 0, 0, 0]),
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn execute_sync_lock(
+    env: &mut Env,
+    user_actor: &str,
+    challenge_id: u32,
+    debug_data: Option<(bool, u8, bool)>,
+) -> Result<(), SolanaKiteError> {
+    execute_team_sync_lock(env, user_actor, challenge_id, None, debug_data)
+}
+
+pub fn execute_team_sync_lock(
+    env: &mut Env,
+    user_actor: &str,
+    challenge_id: u32,
+    team_id: Option<u8>,
+    debug_data: Option<(bool, u8, bool)>,
+) -> Result<(), SolanaKiteError> {
+    let user = map_actor_from_id(env, user_actor);
+    let instructions = build_attested_sync_lock_instructions(
+        env,
+        user.pubkey(),
+        challenge_id,
+        team_id,
+        debug_data,
+    );
+
+    send_transaction_from_instructions(&mut env.litesvm, instructions, &[user], &user.pubkey())?;
+
+    Ok(())
+}
+
+pub fn execute_delegated_sync_lock(
+    env: &mut Env,
+    user_actor: &str,
+    delegate: &Keypair,
+    challenge_id: u32,
+    debug_data: Option<(bool, u8, bool)>,
+) -> Result<(), SolanaKiteError> {
+    let user = map_actor_from_id(env, user_actor);
+    let mut instructions =
+        build_attested_sync_lock_instructions(env, user.pubkey(), challenge_id, None, debug_data);
+
+    // dev : participant wallet stays unsigned, delegate signs and pays fees
+    let sync_lock = instructions.last_mut().unwrap();
+    sync_lock.accounts[0].is_signer = false;
+    sync_lock
+        .accounts
+        .push(AccountMeta::new(delegate.pubkey(), true));
+
+    send_transaction_from_instructions(
+        &mut env.litesvm,
+        instructions,
+        &[delegate],
+        &delegate.pubkey(),
+    )?;
+
+    Ok(())
+}
+
+// dev : the attestation SyncLock accepts next for user, its day follows the user_pda's next_sync_day
+pub fn build_next_sync_attestation(
+    env: &Env,
+    user: Pubkey,
+    challenge_id: u32,
+    user_passed: bool,
+    days_not_synced: u8,
+) -> SyncAttestation {
+    let next_sync_day = env
+        .litesvm
+        .get_account(&get_user_pda(user))
+        .and_then(|account| User::read(&account.data).ok())
+        .map_or(0, |user_pda| user_pda.next_sync_day);
+
+    SyncAttestation {
+        user_passed,
+        days_not_synced,
+        user: user.to_bytes(),
+        report: DeviceReport {
+            challenge_id,
+            day: next_sync_day + days_not_synced as u32,
+            screen_time: 0,
+        },
+    }
+}
+
+// dev : ed25519 instruction with the oracle's signature over the attestation
+pub fn build_sync_attestation_instruction(
+    oracle: &Keypair,
+    attestation: &SyncAttestation,
+) -> Instruction {
+    build_device_attestation_instruction(oracle, attestation, None, &attestation.report)
+}
+
+// dev : device signs `device_report`, which tests set apart from attestation.report to provoke a mismatch
+pub fn build_device_attestation_instruction(
+    oracle: &Keypair,
+    attestation: &SyncAttestation,
+    device: Option<&Keypair>,
+    device_report: &DeviceReport,
+) -> Instruction {
+    let signature = oracle.sign_message(&attestation.to_bytes());
+    let device = device.map(|device| {
+        let signature = device.sign_message(&device_report.to_bytes());
+        (device.pubkey(), signature.into())
+    });
+
+    client::sync_attestation(&oracle.pubkey(), signature.into(), attestation, device)
+}
+
+// dev : mock-oracle builds take debug_data as is, other builds get the same result attested by env.oracle
+// debug_data None means a passed day with nothing missed
+pub fn build_attested_sync_lock_instructions(
+    env: &Env,
+    user: Pubkey,
+    challenge_id: u32,
+    team_id: Option<u8>,
+    debug_data: Option<(bool, u8, bool)>,
+) -> Vec<Instruction> {
+    if cfg!(feature = "mock-oracle") && debug_data.is_some() {
+        return vec![build_sync_lock_instruction(
+            user,
+            challenge_id,
+            team_id,
+            debug_data,
+        )];
+    }
+
+    let (user_passed, days_not_synced, _) = debug_data.unwrap_or((true, 0, false));
+
+    vec![
+        build_sync_attestation_instruction(
+            &env.oracle,
+            &build_next_sync_attestation(env, user, challenge_id, user_passed, days_not_synced),
+        ),
+        build_sync_lock_instruction(user, challenge_id, team_id, None),
+    ]
+}
+
+pub fn build_set_sync_delegate_instruction(
+    user: Pubkey,
+    delegate: Pubkey,
+    delegate_expiry: i64,
+) -> Instruction {
+    let program_id = get_program_id();
+
+    let accounts = vec![
+        AccountMeta::new_readonly(user, true),
+        AccountMeta::new(get_user_pda(user), false),
+        AccountMeta::new_readonly(Pubkey::from(CLOCK_ID.to_bytes()), false),
+    ];
+
+    let mut data = vec![13u8];
+    data.extend_from_slice(delegate.as_ref());
+    data.extend_from_slice(&delegate_expiry.to_le_bytes());
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn execute_set_sync_delegate(
+    env: &mut Env,
+    user_actor: &str,
+    delegate: Pubkey,
+    delegate_expiry: i64,
+) -> Result<(), SolanaKiteError> {
+    let user = map_actor_from_id(env, user_actor);
+    let instruction = build_set_sync_delegate_instruction(user.pubkey(), delegate, delegate_expiry);
+
+    send_transaction_from_instructions(
+        &mut env.litesvm,
+        vec![instruction],
+        &[user],
+        &user.pubkey(),
+    )?;
+
+    Ok(())
+}
+
+pub fn build_set_oracle_instruction(admin: Pubkey, oracle: Pubkey) -> Instruction {
+    let program_id = get_program_id();
+    let (global_pda, _) = get_pda_and_bump(&[b"global".as_ref().into()], &program_id);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(admin, true),
+        AccountMeta::new(global_pda, false),
+    ];
+
+    let mut data = vec![14u8];
+    data.extend_from_slice(oracle.as_ref());
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn execute_set_oracle(
+    env: &mut Env,
+    admin_actor: &str,
+    oracle: Pubkey,
+) -> Result<(), SolanaKiteError> {
+    let admin = map_actor_from_id(env, admin_actor);
+    let instruction = build_set_oracle_instruction(admin.pubkey(), oracle);
+
+    send_transaction_from_instructions(
+        &mut env.litesvm,
+        vec![instruction],
+        &[admin],
+        &admin.pubkey(),
+    )?;
+
+    Ok(())
+}
+
+// dev : results are (user, user_passed, days_not_synced)
+pub fn build_batch_sync_instruction(
+    oracle: Pubkey,
+    challenge_id: u32,
+    results: &[(Pubkey, bool, u8)],
+) -> Instruction {
+    let program_id = get_program_id();
+    let (global_pda, _) = get_pda_and_bump(&[b"global".as_ref().into()], &program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(oracle, true),
+        AccountMeta::new(global_pda, false),
+        AccountMeta::new(get_challenge_pda(challenge_id), false),
+        AccountMeta::new_readonly(Pubkey::from(CLOCK_ID.to_bytes()), false),
+    ];
+
+    let mut data = vec![15u8];
+    data.extend_from_slice(&challenge_id.to_le_bytes());
+
+    for (user, user_passed, days_not_synced) in results {
+        accounts.push(AccountMeta::new(get_user_pda(*user), false));
+        data.extend_from_slice(&[*user_passed as u8, *days_not_synced]);
+    }
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn execute_batch_sync(
+    env: &mut Env,
+    oracle: &Keypair,
+    challenge_id: u32,
+    results: &[(Pubkey, bool, u8)],
+) -> Result<(), SolanaKiteError> {
+    let instruction = build_batch_sync_instruction(oracle.pubkey(), challenge_id, results);
+
+    send_transaction_from_instructions(
+        &mut env.litesvm,
+        vec![instruction],
+        &[oracle],
+        &oracle.pubkey(),
+    )?;
+
+    Ok(())
+}
+
+pub fn build_excuse_days_instruction(
+    authority: Pubkey,
+    challenge_id: u32,
+    start: i64,
+    end: i64,
+) -> Instruction {
+    let program_id = get_program_id();
+    let (global_pda, _) = get_pda_and_bump(&[b"global".as_ref().into()], &program_id);
+
+    let accounts = vec![
+        AccountMeta::new(authority, true),
+        AccountMeta::new_readonly(global_pda, false),
+        AccountMeta::new(get_excusal_pda(challenge_id), false),
+        AccountMeta::new_readonly(Pubkey::from(SYSTEM_ID.to_bytes()), false),
+    ];
+
+    let mut data = vec![20u8];
+    data.extend_from_slice(&challenge_id.to_le_bytes());
+    data.extend_from_slice(&start.to_le_bytes());
+    data.extend_from_slice(&end.to_le_bytes());
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn execute_excuse_days(
+    env: &mut Env,
+    authority: &Keypair,
+    challenge_id: u32,
+    start: i64,
+    end: i64,
+) -> Result<(), SolanaKiteError> {
+    let instruction = build_excuse_days_instruction(authority.pubkey(), challenge_id, start, end);
+
+    send_transaction_from_instructions(
+        &mut env.litesvm,
+        vec![instruction],
+        &[authority],
+        &authority.pubkey(),
+    )?;
+
+    Ok(())
+}
+
+pub fn build_withdraw_instruction(user: Pubkey, challenge_id: u32) -> Instruction {
+    let program_id = get_program_id();
+    let (global_pda, _) = get_pda_and_bump(&[b"global".as_ref().into()], &program_id);
+
+    let accounts = vec![
+        AccountMeta::new(user, true),
+        AccountMeta::new(global_pda, false),
+        AccountMeta::new(get_challenge_pda(challenge_id), false),
+        AccountMeta::new(get_user_pda(user), false),
+        AccountMeta::new_readonly(Pubkey::from(CLOCK_ID.to_bytes()), false),
+        AccountMeta::new_readonly(Pubkey::from(SYSTEM_ID.to_bytes()), false),
+    ];
+
+    let mut data = vec![3u8];
+    data.extend_from_slice(&challenge_id.to_le_bytes());
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn execute_withdraw(
+    env: &mut Env,
+    user_actor: &str,
+    challenge_id: u32,
+) -> Result<(), SolanaKiteError> {
+    let user = map_actor_from_id(env, user_actor);
+    let instruction = build_withdraw_instruction(user.pubkey(), challenge_id);
+
+    send_transaction_from_instructions(
+        &mut env.litesvm,
+        vec![instruction],
+        &[user],
+        &user.pubkey(),
+    )?;
+
+    Ok(())
+}
+
+pub fn build_settle_duel_instruction(
+    caller: Pubkey,
+    challenge_id: u32,
+    creator: Pubkey,
+    opponent: Pubkey,
+) -> Instruction {
+    let program_id = get_program_id();
+
+    let accounts = vec![
+        AccountMeta::new(caller, true),
+        AccountMeta::new(get_challenge_pda(challenge_id), false),
+        AccountMeta::new(creator, false),
+        AccountMeta::new(opponent, false),
+        AccountMeta::new_readonly(get_user_pda(creator), false),
+        AccountMeta::new_readonly(get_user_pda(opponent), false),
+        AccountMeta::new_readonly(Pubkey::from(CLOCK_ID.to_bytes()), false),
+    ];
+
+    let mut data = vec![11u8];
+    data.extend_from_slice(&challenge_id.to_le_bytes());
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn execute_settle_duel(
+    env: &mut Env,
+    caller_actor: &str,
+    challenge_id: u32,
+    creator: Pubkey,
+    opponent: Pubkey,
+) -> Result<(), SolanaKiteError> {
+    let caller = map_actor_from_id(env, caller_actor);
+    let instruction =
+        build_settle_duel_instruction(caller.pubkey(), challenge_id, creator, opponent);
+
+    send_transaction_from_instructions(
+        &mut env.litesvm,
+        vec![instruction],
+        &[caller],
+        &caller.pubkey(),
+    )?;
+
+    Ok(())
+}
+
+pub fn build_claim_winner_position_instruction(
+    user: Pubkey,
+    challenge_id: u32,
+    team_id: Option<u8>,
+) -> Instruction {
+    let program_id = get_program_id();
+
+    let mut accounts = vec![
+        AccountMeta::new(user, true),
+        AccountMeta::new(get_challenge_pda(challenge_id), false),
+        AccountMeta::new_readonly(get_user_pda(user), false),
+        AccountMeta::new_readonly(Pubkey::from(CLOCK_ID.to_bytes()), false),
+    ];
+    if let Some(team_id) = team_id {
+        accounts.push(AccountMeta::new_readonly(
+            get_team_pda(challenge_id, team_id),
+            false,
+        ));
+    }
+
+    let mut data = vec![4u8];
+    data.extend_from_slice(&challenge_id.to_le_bytes());
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn execute_claim_winner_position(
+    env: &mut Env,
+    user_actor: &str,
+    challenge_id: u32,
+    team_id: Option<u8>,
+) -> Result<(), SolanaKiteError> {
+    let user = map_actor_from_id(env, user_actor);
+    let instruction = build_claim_winner_position_instruction(user.pubkey(), challenge_id, team_id);
+
+    send_transaction_from_instructions(
+        &mut env.litesvm,
+        vec![instruction],
+        &[user],
+        &user.pubkey(),
+    )?;
+
+    Ok(())
+}
+
+// dev : plants account bytes as an older program version would have left them
+pub fn set_legacy_account(env: &mut Env, address: Pubkey, data: Vec<u8>) {
+    let lamports = env.litesvm.minimum_balance_for_rent_exemption(data.len());
+
+    env.litesvm
+        .set_account(
+            address,
+            Account {
+                lamports,
+                data,
+                owner: env.program_id,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+}
+
+pub fn build_migrate_account_instruction(payer: Pubkey, account: Pubkey) -> Instruction {
+    let program_id = get_program_id();
+    let accounts = vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new(account, false),
+        AccountMeta::new_readonly(Pubkey::from(SYSTEM_ID.to_bytes()), false),
+    ];
+
+    let data = vec![16u8];
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+pub fn execute_migrate_account(
+    env: &mut Env,
+    payer_actor: &str,
+    account: Pubkey,
+) -> Result<(), SolanaKiteError> {
+    let payer = map_actor_from_id(env, payer_actor);
+    let instruction = build_migrate_account_instruction(payer.pubkey(), account);
+
+    send_transaction_from_instructions(
+        &mut env.litesvm,
+        vec![instruction],
+        &[payer],
+        &payer.pubkey(),
+    )?;
+
+    Ok(())
+}
+
+// dev : same as send_transaction_from_instructions() but returns the compute units the instruction used
+pub fn send_and_measure(
+    env: &mut Env,
+    instruction: Instruction,
+    signers: &[&Keypair],
+    payer: &Pubkey,
+) -> Result<u64, SolanaKiteError> {
+    send_all_and_measure(env, &[instruction], signers, payer)
+}
+
+pub fn send_all_and_measure(
+    env: &mut Env,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+    payer: &Pubkey,
+) -> Result<u64, SolanaKiteError> {
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(payer),
+        signers,
+        env.litesvm.latest_blockhash(),
+    );
+
+    env.litesvm
+        .send_transaction(transaction)
+        .map(|meta| meta.compute_units_consumed)
+        .map_err(|e| SolanaKiteError::TransactionFailed(format!("{:?}", e)))
+}
+
+// dev : litesvm reports instruction errors by the same debug name ProgramError uses
+pub fn assert_program_error<T: core::fmt::Debug>(
+    result: Result<T, SolanaKiteError>,
+    error: impl Into<ProgramError>,
+    msg: &str,
+) {
+    let expected = format!("{:?}", error.into());
+    match result {
+        Err(SolanaKiteError::TransactionFailed(failure)) => assert!(
+            failure.contains(&expected),
+            "{msg}: expected {expected}, got {failure}"
+        ),
+        other => panic!("{msg}: expected {expected}, got {other:?}"),
+    }
+}
+
+// dev : runs a view instruction without landing it and returns its return data
+pub fn simulate_view(env: &mut Env, instruction: Instruction) -> Result<Vec<u8>, SolanaKiteError> {
+    let payer = env.admin.insecure_clone();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[&payer],
+        env.litesvm.latest_blockhash(),
+    );
+
+    env.litesvm
+        .simulate_transaction(transaction)
+        .map(|simulation| simulation.meta.return_data.data)
+        .map_err(|e| SolanaKiteError::TransactionFailed(format!("{:?}", e)))
+}